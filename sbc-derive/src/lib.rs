@@ -17,29 +17,40 @@
 // @author Thomas Chiantia <thomas.chiantia@gmail.com>
 // @date 2022
 
-#[cfg(test)]
-mod tests;
-
-mod attributes;
-mod context;
-mod field;
-mod keyword;
-mod path;
-mod utils;
-
-use attributes::ContainerAttributes;
-use context::Context;
 use proc_macro::TokenStream;
 use quote::quote;
+use serde_bindgen_core_model::attributes::{ContainerAttributes, Features, RenameRule};
+use serde_bindgen_core_model::context::Context;
+use serde_bindgen_core_model::naming::Naming;
 use syn::parse_macro_input;
 
+/// The features of the library which turn on bindings, forwarded to this crate
+fn features() -> Features {
+    let features = [
+        ("init", cfg!(feature = "init")),
+        ("copy", cfg!(feature = "copy")),
+        ("parse", cfg!(feature = "parse")),
+        ("print", cfg!(feature = "print")),
+        ("protobuf", cfg!(feature = "protobuf")),
+        ("cbor", cfg!(feature = "cbor")),
+        ("msgpack", cfg!(feature = "msgpack")),
+    ];
+    Features(
+        features
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+            .collect(),
+    )
+}
+
 #[proc_macro_attribute]
 pub fn binding(attr: TokenStream, item: TokenStream) -> TokenStream {
     let container_attributes = parse_macro_input!(attr as ContainerAttributes);
-    let prefix = container_attributes
-        .seek_val("prefix")
-        .map(|lit| lit.value())
-        .unwrap_or_else(|| "sbc".to_string());
+    let naming = match Naming::from_container(&container_attributes) {
+        Ok(naming) => naming,
+        Err(e) => return e.to_compile_error().into(),
+    };
     let rename_all = container_attributes
         .seek_val("rename_all")
         .map(|lit| {
@@ -48,64 +59,178 @@ pub fn binding(attr: TokenStream, item: TokenStream) -> TokenStream {
         })
         .unwrap_or(None);
 
+    // the bindings selected by the cargo features and container options
+    let features = features();
+    let bindings = match container_attributes.bindings(&features) {
+        Ok(bindings) => bindings,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let table = match container_attributes.table() {
+        Ok(table) => table,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
     // Parse the callers decorated struct
-    let ctx: Context = parse_macro_input!(item);
+    let mut ctx: Context = parse_macro_input!(item);
+    ctx.rename_all = container_attributes
+        .seek_val("rename_all")
+        .and_then(|lit| RenameRule::parse(&lit.value()));
 
     // create a type alias
     let (ident_original, ident_borrowed, _ident_owned) = ctx.path.split_self_for_impl();
 
+    // create the type aliases named by the container options
+    let aliases = naming.aliases(&ctx.path);
+
     // create an "owned" version of the struct. (no references)
     let owned = ctx.clone().into_owned();
 
+    // create a "view" version of the struct. (pointer and length pairs)
+    let view = ctx.clone().into_view();
+
+    // create a "partial" version of the struct. (every field optional)
+    let partial = ctx.partial();
+
     // create a const FOO: usize = max_len block
     let impl_weight = ctx.impl_weight();
 
+    // create a const FOO_CBOR_MAX_LEN: usize = max_len block
+    let impl_cbor_weight = ctx.impl_cbor_weight();
+
+    // create a const FOO_MSGPACK_MAX_LEN: usize = max_len block
+    let impl_msgpack_weight = ctx.impl_msgpack_weight();
+
+    // create the C layout of the owned struct, asserted against rustc's
+    let impl_layout = ctx.impl_layout();
+
+    // assert the borrowed struct fits the view C stores it as
+    let impl_view_layout = ctx.impl_view_layout();
+
+    // create a hash of the layout, to check the ABI at run time
+    let impl_abi_hash = ctx.impl_abi_hash();
+
+    // create the protobuf encoding of the owned struct
+    let impl_protobuf = match features.has("protobuf") {
+        true => match ctx.impl_protobuf() {
+            Ok(impl_protobuf) => Some(impl_protobuf),
+            Err(e) => return e.to_compile_error().into(),
+        },
+        false => None,
+    };
+
     // create impl Default block
     let impl_default = ctx.impl_default();
 
+    // create in place init and copy impls, used by the init and copy bindings
+    let impl_init = ctx.impl_init();
+    let impl_copy_from = ctx.impl_copy_from();
+
     // create impl From block
     let impl_from_owned = ctx.impl_from_owned();
 
     // create impl From block
     let impl_from_ref = ctx.impl_from_ref();
 
-    // create binding for copy function
-    let binding_copy = ctx.binding_copy(&prefix);
-
-    // create binding for init function
-    let binding_init = ctx.binding_init(&prefix);
-
-    // create binding for parse function
-    let binding_parse = ctx.binding_parse(&prefix);
-
-    // create binding for parse function
-    let binding_print = ctx.binding_print(&prefix);
+    // create impl From block
+    let impl_view_from_ref = ctx.impl_view_from_ref();
 
-    // create binding for parse function
-    let binding_print_owned = ctx.binding_print_owned(&prefix);
+    // create impl From block
+    let impl_from_view = ctx.impl_from_view();
+
+    // create impl Chunk block
+    let impl_chunk = ctx.impl_chunk();
+
+    // create impl merge block
+    let impl_merge = ctx.impl_merge();
+
+    // create impl Diff block
+    let impl_diff = ctx.impl_diff();
+
+    // create impl Pointer block
+    let impl_pointer = ctx.impl_pointer();
+
+    // create impl Feed block
+    let impl_feed = ctx.impl_feed();
+
+    // create impl Binding block
+    let impl_binding = ctx.impl_binding();
+
+    // create the extern "C" functions of the enabled bindings
+    let bindings = ctx.bindings(&naming, &bindings, table);
+
+    // In table mode the shared engine does the work, so there is no serde.
+    // Its parse merges into the owned struct, see serde_bindgen_core::table
+    if table {
+        let owned = owned.strip_serde();
+        let quoted = quote! {
+            #[no_mangle]
+            #impl_weight
+            #impl_cbor_weight
+            #impl_msgpack_weight
+            #[no_mangle]
+            pub type #ident_borrowed = #ident_original;
+            #aliases
+            #ctx
+            #[repr(C)]
+            #owned
+            #impl_layout
+            #impl_abi_hash
+            #impl_protobuf
+            #impl_default
+            #impl_init
+            #impl_from_owned
+            #impl_from_ref
+            #bindings
+        };
+        return proc_macro::TokenStream::from(quoted);
+    }
 
     // render all the new items
     let quoted = quote! {
         #[no_mangle]
         #impl_weight
+        #impl_cbor_weight
+        #impl_msgpack_weight
         #[no_mangle]
         pub type #ident_borrowed = #ident_original;
+        #aliases
         #[repr(C)]
-        #[derive(serde::Deserialize)]
-        #[derive(serde::Serialize)]
-        #[serde(crate="self::serde")]
+        #[derive(serde_bindgen_core::serde::Deserialize)]
+        #[derive(serde_bindgen_core::serde::Serialize)]
+        #[serde(crate="serde_bindgen_core::serde")]
         #rename_all
         #ctx
         #[repr(C)]
+        #[derive(serde_bindgen_core::serde::Deserialize)]
+        #[derive(serde_bindgen_core::serde::Serialize)]
+        #[serde(crate="serde_bindgen_core::serde")]
+        #rename_all
         #owned
+        #[repr(C)]
+        #view
+        #[derive(serde_bindgen_core::serde::Deserialize)]
+        #[serde(crate="serde_bindgen_core::serde")]
+        #rename_all
+        #partial
+        #impl_layout
+        #impl_view_layout
+        #impl_abi_hash
+        #impl_protobuf
         #impl_default
+        #impl_init
+        #impl_copy_from
         #impl_from_owned
         #impl_from_ref
-        #binding_copy
-        #binding_init
-        #binding_parse
-        #binding_print
-        #binding_print_owned
+        #impl_view_from_ref
+        #impl_from_view
+        #impl_binding
+        #impl_chunk
+        #impl_feed
+        #impl_pointer
+        #impl_merge
+        #impl_diff
+        #bindings
     };
     proc_macro::TokenStream::from(quoted)
 }
@@ -0,0 +1,154 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! sbc-gen: describe the `#[binding]` structs of a crate to other languages
+//!
+//! ```text
+//! sbc-gen [-f c|cpp|schema|ts|python|proto] [-o bindings.h]
+//!         [--features <list>] [--no-default-features] <file or directory>...
+//! ```
+//!
+//! Directories are searched recursively for `.rs` files. The format is a C
+//! header unless another is given, and it is written to stdout unless an
+//! output file is given. The C++ header includes the C header named like its
+//! output file, so generate both: `-f c -o foo.h` and `-f cpp -o foo.hpp`.
+//! The bindings declared follow the features given as cargo takes them, so
+//! pass the features the library is built with, IE: `--features cbor`.
+
+use serde_bindgen_core_model::attributes::Features;
+use serde_bindgen_core_model::{cpp, header, proto, python, schema, typescript};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+/// Collect the rust sources under path, sorted so the output is stable
+fn sources(path: &Path, found: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if path.is_dir() {
+        let mut entries = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<PathBuf>>>()?;
+        entries.sort();
+        for entry in entries {
+            sources(&entry, found)?;
+        }
+    } else if path.extension().is_some_and(|ext| ext == "rs") {
+        found.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+/// The include guard for the output file. IE: `bindings.h` => `BINDINGS_H`
+fn guard(output: Option<&Path>) -> String {
+    let name = output
+        .and_then(|path| path.file_name())
+        .map_or("SBC_BINDINGS_H".into(), |name| name.to_string_lossy());
+    name.chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_uppercase(),
+            false => '_',
+        })
+        .collect()
+}
+
+/// The C header a C++ header includes. IE: `bindings.hpp` => `bindings.h`
+fn include(output: Option<&Path>) -> String {
+    output
+        .and_then(|path| path.file_name())
+        .map_or("bindings.h".into(), |name| {
+            Path::new(name)
+                .with_extension("h")
+                .to_string_lossy()
+                .into_owned()
+        })
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "usage: sbc-gen [-f c|cpp|schema|ts|python|proto] [-o <output>] [--features <list>] \
+         [--no-default-features] <file or directory>..."
+    );
+    exit(2)
+}
+
+fn fail(path: &Path, error: impl std::fmt::Display) -> ! {
+    eprintln!("sbc-gen: {}: {}", path.display(), error);
+    exit(1)
+}
+
+fn main() {
+    let mut output = None;
+    let mut format = String::from("c");
+    let mut inputs = Vec::new();
+    let mut features = Features::default();
+    let mut listed = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => {
+                output = Some(PathBuf::from(args.next().unwrap_or_else(|| usage())))
+            }
+            "-f" | "--format" => format = args.next().unwrap_or_else(|| usage()),
+            "-F" | "--features" => listed.push(args.next().unwrap_or_else(|| usage())),
+            "--no-default-features" => features = Features(Vec::new()),
+            "-h" | "--help" => usage(),
+            _ => inputs.push(PathBuf::from(arg)),
+        }
+    }
+    if inputs.is_empty() {
+        usage();
+    }
+    for list in &listed {
+        features.extend(list).unwrap_or_else(|e| {
+            eprintln!("sbc-gen: {}", e);
+            exit(2)
+        });
+    }
+
+    let mut files = Vec::new();
+    for input in &inputs {
+        sources(input, &mut files).unwrap_or_else(|e| fail(input, e));
+    }
+
+    let mut found = Vec::new();
+    for file in &files {
+        let source = std::fs::read_to_string(file).unwrap_or_else(|e| fail(file, e));
+        let parsed = syn::parse_file(&source).unwrap_or_else(|e| fail(file, e));
+        header::scan(&parsed.items, &mut found).unwrap_or_else(|e| fail(file, e));
+    }
+
+    let rendered = match format.as_str() {
+        "c" => header::render(&found, &guard(output.as_deref()), &features),
+        "schema" => schema::render(&found),
+        "cpp" => cpp::render(
+            &found,
+            &guard(output.as_deref()),
+            &include(output.as_deref()),
+            &features,
+        ),
+        "ts" => typescript::render(&found),
+        "python" => python::render(&found, &features),
+        "proto" => proto::render(&found),
+        _ => usage(),
+    };
+    let rendered =
+        rendered.unwrap_or_else(|e| fail(output.as_deref().unwrap_or(Path::new("-")), e));
+    match &output {
+        Some(path) => std::fs::write(path, rendered).unwrap_or_else(|e| fail(path, e)),
+        None => print!("{}", rendered),
+    }
+}
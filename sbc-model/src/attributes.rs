@@ -0,0 +1,579 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use proc_macro2::{Span, TokenStream};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::token::Bracket;
+use syn::ExprArray;
+use syn::Ident;
+use syn::LitInt;
+use syn::Token;
+
+use quote::ToTokens;
+
+use super::keyword;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub struct ContainerAttribute {
+    pub ident: Ident,
+    pub eq: Token![=],
+    pub val: ContainerValue,
+}
+
+impl Parse for ContainerAttribute {
+    fn parse(input: ParseStream) -> Result<ContainerAttribute> {
+        Ok(ContainerAttribute {
+            ident: input.parse()?,
+            eq: input.parse()?,
+            val: input.parse()?,
+        })
+    }
+}
+
+/// A container option is a string, or a list of strings. IE:
+/// `prefix = "foo"` or `skip = ["print", "copy"]`
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub enum ContainerValue {
+    Str(syn::LitStr),
+    List(Punctuated<syn::LitStr, Token![,]>),
+}
+
+impl Parse for ContainerValue {
+    fn parse(input: ParseStream) -> Result<ContainerValue> {
+        if input.peek(Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            Ok(ContainerValue::List(Punctuated::parse_terminated(
+                &content,
+            )?))
+        } else {
+            Ok(ContainerValue::Str(input.parse()?))
+        }
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub struct ContainerAttributes(pub Punctuated<ContainerAttribute, Token![,]>);
+impl Parse for ContainerAttributes {
+    fn parse(input: ParseStream) -> Result<ContainerAttributes> {
+        let punctuated = Punctuated::parse_terminated(input)?;
+        Ok(ContainerAttributes(punctuated))
+    }
+}
+impl ContainerAttributes {
+    pub fn seek_val(&self, find: &str) -> Option<&syn::LitStr> {
+        self.0.iter().find_map(|attr| match &attr.val {
+            ContainerValue::Str(val) if attr.ident == find => Some(val),
+            _ => None,
+        })
+    }
+
+    pub fn seek_list(&self, find: &str) -> Option<&Punctuated<syn::LitStr, Token![,]>> {
+        self.0.iter().find_map(|attr| match &attr.val {
+            ContainerValue::List(list) if attr.ident == find => Some(list),
+            _ => None,
+        })
+    }
+
+    /// True for mode = "table", false for the default mode = "serde"
+    pub fn table(&self) -> Result<bool> {
+        match self.seek_val("mode") {
+            None => Ok(false),
+            Some(lit) if lit.value() == "serde" => Ok(false),
+            Some(lit) if lit.value() == "table" => Ok(true),
+            Some(lit) => {
+                let msg = "expected mode = \"serde\" or mode = \"table\"";
+                Err(syn::Error::new(lit.span(), msg))
+            }
+        }
+    }
+
+    /// The bindings to generate. The features select the defaults, and the
+    /// container options "only" and "skip" refine them. Naming a binding
+    /// which the mode does not generate, or which "only" names without its
+    /// feature, is an error
+    pub fn bindings(&self, features: &Features) -> Result<Bindings> {
+        let (mode, all) = match self.table()? {
+            true => ("table", TABLE_BINDINGS),
+            false => ("serde", SERDE_BINDINGS),
+        };
+        let names = |list: &Punctuated<syn::LitStr, Token![,]>| {
+            list.iter()
+                .map(|lit| match all.iter().find(|name| **name == lit.value()) {
+                    Some(name) => Ok(*name),
+                    None if SERDE_BINDINGS
+                        .iter()
+                        .chain(TABLE_BINDINGS)
+                        .any(|n| *n == lit.value()) =>
+                    {
+                        let msg = format!("no {} binding in mode = \"{}\"", lit.value(), mode);
+                        Err(syn::Error::new(lit.span(), msg))
+                    }
+                    None => {
+                        let msg = format!("unknown binding, expected one of {:?}", all);
+                        Err(syn::Error::new(lit.span(), msg))
+                    }
+                })
+                .collect::<Result<Vec<&'static str>>>()
+        };
+        let mut enabled = match self.seek_list("only") {
+            Some(only) => {
+                for lit in only {
+                    match Features::requires(&lit.value()) {
+                        Some(feature) if !features.has(feature) => {
+                            let msg = format!("{} requires the {} feature", lit.value(), feature);
+                            return Err(syn::Error::new(lit.span(), msg));
+                        }
+                        _ => {}
+                    }
+                }
+                names(only)?
+            }
+            None => all
+                .iter()
+                .copied()
+                .filter(|name| features.enable(name))
+                .collect(),
+        };
+        if let Some(skip) = self.seek_list("skip") {
+            let skip = names(skip)?;
+            enabled.retain(|name| !skip.contains(name));
+        }
+        // The table engine walks the descriptor of the struct
+        if let Some(name) = enabled
+            .iter()
+            .find(|name| mode == "table" && (**name == "parse_owned" || **name == "print_owned"))
+        {
+            if !enabled.contains(&"descriptor") {
+                let span = self
+                    .seek_list("skip")
+                    .into_iter()
+                    .chain(self.seek_list("only"))
+                    .flatten()
+                    .find(|lit| lit.value() == "descriptor" || lit.value() == *name)
+                    .map_or_else(Span::call_site, |lit| lit.span());
+                let msg = format!("{} requires the descriptor binding", name);
+                return Err(syn::Error::new(span, msg));
+            }
+        }
+        Ok(Bindings(enabled))
+    }
+}
+
+/// The bindings which the "only" and "skip" container options can name,
+/// in mode = "serde"
+const SERDE_BINDINGS: &[&str] = &[
+    "init",
+    "copy",
+    "parse",
+    "parse_feed",
+    "merge",
+    "diff",
+    "changed",
+    "get",
+    "set",
+    "print",
+    "print_owned",
+    "write_owned",
+    "print_chunk",
+    "print_view",
+    "abi_hash",
+    "descriptor",
+    "pb_encode",
+    "pb_decode",
+    "cbor_parse",
+    "cbor_print",
+    "msgpack_parse",
+    "msgpack_print",
+];
+
+/// The bindings of mode = "table"
+const TABLE_BINDINGS: &[&str] = &[
+    "init",
+    "parse_owned",
+    "print_owned",
+    "abi_hash",
+    "descriptor",
+    "pb_encode",
+    "pb_decode",
+];
+
+/// The cargo features of the library which turn on bindings crate wide. The
+/// derive reads them from its own features, and sbc-gen from its command line
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug, PartialEq))]
+pub struct Features(pub Vec<&'static str>);
+
+impl Features {
+    /// Every feature which turns on bindings
+    pub const ALL: &'static [&'static str] = &[
+        "init", "copy", "parse", "print", "protobuf", "cbor", "msgpack",
+    ];
+
+    /// The default features of the library
+    pub const DEFAULT: &'static [&'static str] = &["init", "copy", "parse", "print"];
+
+    pub fn has(&self, name: &str) -> bool {
+        self.0.contains(&name)
+    }
+
+    /// Add a comma separated list of features, as cargo's `--features` takes
+    pub fn extend(&mut self, list: &str) -> std::result::Result<(), String> {
+        for name in list
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            match Features::ALL.iter().find(|feature| **feature == name) {
+                Some(feature) if self.has(feature) => {}
+                Some(feature) => self.0.push(feature),
+                None => {
+                    let msg = format!(
+                        "unknown feature {}, expected one of {:?}",
+                        name,
+                        Features::ALL
+                    );
+                    return Err(msg);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the features turn on the binding
+    fn enable(&self, binding: &str) -> bool {
+        match binding {
+            "init" => self.has("init"),
+            "copy" => self.has("copy"),
+            "parse" | "parse_feed" | "parse_owned" | "merge" | "set" => self.has("parse"),
+            "print" | "print_owned" | "write_owned" | "print_chunk" | "print_view" | "diff"
+            | "get" => self.has("print"),
+            _ => Features::requires(binding).is_none_or(|feature| self.has(feature)),
+        }
+    }
+
+    /// The feature a binding can not be generated without, as it calls into
+    /// the module of the library behind that feature
+    fn requires(binding: &str) -> Option<&'static str> {
+        match binding {
+            "pb_encode" | "pb_decode" => Some("protobuf"),
+            "cbor_parse" | "cbor_print" => Some("cbor"),
+            "msgpack_parse" | "msgpack_print" => Some("msgpack"),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Features {
+    fn default() -> Features {
+        Features(Features::DEFAULT.to_vec())
+    }
+}
+
+/// The names of the bindings to generate
+#[cfg_attr(feature = "testing", derive(Debug, PartialEq))]
+pub struct Bindings(pub Vec<&'static str>);
+
+impl Bindings {
+    pub fn has(&self, name: &str) -> bool {
+        self.0.contains(&name)
+    }
+}
+
+/// The container "rename_all" option. Mirrors the serde rules for fields so
+/// that code which prints keys itself agrees with the serde derive.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub fn parse(rule: &str) -> Option<RenameRule> {
+        match rule {
+            "lowercase" => Some(RenameRule::Lower),
+            "UPPERCASE" => Some(RenameRule::Upper),
+            "PascalCase" => Some(RenameRule::Pascal),
+            "camelCase" => Some(RenameRule::Camel),
+            "snake_case" => Some(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnake),
+            "kebab-case" => Some(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(RenameRule::ScreamingKebab),
+            _ => None,
+        }
+    }
+
+    pub fn apply_to_field(&self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// An attribute is a doc comment above a struct field identifier.
+///
+/// An attribute is either something we care about, or something we ignore.
+///
+/// If an attribute is something we care about, we store the key value pairs as
+/// field configuration options. We also consume the attribute and do not
+/// render the attribute.
+///
+/// If an attribute is something we do not care about, we ignore it and render
+/// it back verbatim.
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub enum Attribute {
+    Ours(AttributeOurs),
+    Ignore(AttributeDoc),
+}
+
+impl Attribute {
+    pub fn ours(&self) -> Option<&AttributeOurs> {
+        match &self {
+            Attribute::Ours(o) => Some(o),
+            _ => None,
+        }
+    }
+    pub fn ignore(&self) -> Option<&AttributeDoc> {
+        match &self {
+            Attribute::Ignore(i) => Some(i),
+            _ => None,
+        }
+    }
+    pub fn default(&self) -> Option<&DefaultLit> {
+        self.ours()
+            .filter(|meta| meta.meta.key == "default")
+            .map(|meta| &meta.meta.val)
+    }
+    pub fn len(&self) -> Option<LitInt> {
+        self.ours()
+            .filter(|meta| meta.meta.key == "len")
+            .map(|meta| &meta.meta.val)
+            .and_then(|val| val.parse().ok())
+    }
+    pub fn tag(&self) -> Option<LitInt> {
+        self.ours()
+            .filter(|meta| meta.meta.key == "tag")
+            .map(|meta| &meta.meta.val)
+            .and_then(|val| val.parse().ok())
+    }
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let doc: AttributeDoc = input.parse()?;
+        match doc.inner.parse::<MaybeOurs>()? {
+            MaybeOurs::Ours(meta) => Ok(Attribute::Ours(AttributeOurs { doc, meta })),
+            MaybeOurs::Ignore(_) => Ok(Attribute::Ignore(doc)),
+        }
+    }
+}
+
+impl ToTokens for Attribute {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        match self {
+            Attribute::Ours(_) => unimplemented!(),
+            Attribute::Ignore(i) => i.to_tokens(toks),
+        }
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub struct AttributeDoc {
+    pub pound: Token![#],
+    pub bracket: Bracket,
+    pub doc: Ident,
+    pub eq: Token![=],
+    pub inner: syn::LitStr,
+}
+
+impl Parse for AttributeDoc {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let inner;
+        Ok(AttributeDoc {
+            pound: input.parse()?,
+            bracket: syn::bracketed!(inner in input),
+            doc: inner.parse()?,
+            eq: inner.parse()?,
+            inner: inner.parse()?,
+        })
+    }
+}
+
+impl ToTokens for AttributeDoc {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        self.pound.to_tokens(toks);
+        self.bracket.surround(toks, |toks| {
+            self.doc.to_tokens(toks);
+            self.eq.to_tokens(toks);
+            self.inner.to_tokens(toks);
+        })
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub struct AttributeMeta {
+    pub sbc: keyword::sbc,
+    pub col: keyword::Col,
+    pub key: Ident,
+    pub eq: Token![=],
+    pub val: DefaultLit,
+}
+impl Parse for AttributeMeta {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(AttributeMeta {
+            sbc: input.parse()?,
+            col: input.parse()?,
+            key: input.parse()?,
+            eq: input.parse()?,
+            val: input.parse()?,
+        })
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub struct AttributeOurs {
+    pub doc: AttributeDoc,
+    pub meta: AttributeMeta, // <-- Content
+}
+
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub enum MaybeOurs {
+    Ours(AttributeMeta),
+    Ignore(TokenStream),
+}
+impl Parse for MaybeOurs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(keyword::sbc) && input.peek2(keyword::Col) {
+            Ok(MaybeOurs::Ours(input.parse()?))
+        } else {
+            Ok(MaybeOurs::Ignore(input.parse()?))
+        }
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub struct DefaultLit(pub TokenStream);
+impl DefaultLit {
+    /// TODO find a different way to detect an array
+    ///      for some reason we need full features
+    pub fn is_array(&self) -> bool {
+        self.parse::<ExprArray>().map(|_| true).unwrap_or(false)
+    }
+    pub fn parse<T: Parse>(&self) -> Result<T> {
+        syn::parse2(self.0.clone())
+    }
+}
+
+impl From<DefaultLit> for TokenStream {
+    fn from(attr: DefaultLit) -> TokenStream {
+        attr.0
+    }
+}
+
+impl Parse for DefaultLit {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(DefaultLit(input.parse()?))
+    }
+}
+
+impl ToTokens for DefaultLit {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        self.0.to_tokens(toks);
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub struct Attributes(pub Vec<Attribute>);
+impl Attributes {
+    /// Helper to look through an array of attributes and find a "length" prop
+    pub fn seek_len_lit(&self) -> Option<syn::LitInt> {
+        self.0.iter().find_map(|attr| attr.len())
+    }
+
+    /// Helper to look through an array of attributes and find a "length" prop and convert to usize
+    pub fn seek_len(&self) -> usize {
+        self.seek_len_lit()
+            .and_then(|lit| lit.base10_digits().parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Helper to look through an array of attributes and find a protobuf "tag" prop
+    pub fn seek_tag_lit(&self) -> Option<syn::LitInt> {
+        self.0.iter().find_map(|attr| attr.tag())
+    }
+
+    pub fn seek_default(&self) -> Option<&DefaultLit> {
+        self.0.iter().find_map(|attr| attr.default())
+    }
+}
+
+impl From<Vec<Attribute>> for Attributes {
+    fn from(vec: Vec<Attribute>) -> Attributes {
+        Attributes(vec)
+    }
+}
+
+impl From<Attributes> for Vec<Attribute> {
+    fn from(attr: Attributes) -> Vec<Attribute> {
+        attr.0
+    }
+}
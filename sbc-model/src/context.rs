@@ -0,0 +1,2076 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+// quote
+use quote::quote;
+use quote::ToTokens;
+
+// syn::
+use syn::parse::{Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::LitByteStr;
+use syn::Token;
+
+// proc_macro2
+use proc_macro2::{Literal, TokenStream};
+
+use crate::attributes::{Bindings, RenameRule};
+use crate::field::Field;
+use crate::naming::{Form, Naming};
+use crate::path::PathNamed;
+use crate::utils::{self, BinaryFormat};
+
+/// The largest protobuf field number
+const PB_TAG_MAX: u32 = (1 << 29) - 1;
+
+#[derive(Clone)]
+pub struct Context {
+    pub path: PathNamed,
+    pub tok_vis: Option<Token![pub]>,
+    pub tok_struct: Token![struct],
+    pub tok_brace: syn::token::Brace,
+    pub fields: Punctuated<Field, Token![,]>,
+    pub rename_all: Option<RenameRule>,
+}
+
+impl Context {
+    pub fn weight<'a>(&'a self) -> (usize, Vec<(&'a PathNamed, usize)>) {
+        // start with size of 2 for {} brackets
+        let (mut len, remotes) = self.fields.iter().map(|field| field.weight()).fold(
+            (2, Vec::new()),
+            |(acc_len, mut acc_vec), (len, remote)| {
+                if let Some(remote) = remote {
+                    acc_vec.push(remote)
+                }
+                (acc_len + len, acc_vec)
+            },
+        );
+        // add a comma per field except the last
+        len += if self.fields.len() > 0 {
+            self.fields.len() - 1
+        } else {
+            0
+        };
+        (len, remotes)
+    }
+
+    /// The most bytes the struct takes as a map of a binary format, and the
+    /// nested structs with their count
+    pub fn binary_weight(&self, format: &BinaryFormat) -> (usize, Vec<(&PathNamed, usize)>) {
+        let head = (format.map_head)(self.fields.len());
+        self.fields
+            .iter()
+            .map(|field| field.binary_weight(format, self.rename_all))
+            .fold(
+                (head, Vec::new()),
+                |(acc_len, mut acc_vec), (len, remote)| {
+                    acc_vec.extend(remote);
+                    (acc_len + len, acc_vec)
+                },
+            )
+    }
+
+    /// The protobuf field numbers, from `sbc: tag` or else the position of
+    /// the field
+    pub fn pb_tags(&self) -> Result<Vec<u32>> {
+        let mut tags = Vec::new();
+        for (i, field) in self.fields.iter().enumerate() {
+            let tag = match field.attributes.seek_tag_lit() {
+                Some(lit) => match lit.base10_parse::<u32>()? {
+                    tag if tag == 0 || tag > PB_TAG_MAX || (19000..20000).contains(&tag) => {
+                        let msg =
+                            "protobuf field numbers are 1 to 536870911, except 19000 to 19999";
+                        return Err(syn::Error::new(lit.span(), msg));
+                    }
+                    tag => tag,
+                },
+                None => i as u32 + 1,
+            };
+            if tags.contains(&tag) {
+                let msg = format!("protobuf field number {} is used twice", tag);
+                return Err(syn::Error::new(field.ident.span(), msg));
+            }
+            tags.push(tag);
+        }
+        Ok(tags)
+    }
+
+    /// The most bytes the struct encodes to as protobuf, and the nested
+    /// structs with the length of their key and their count
+    pub fn pb_max_len<'a>(&'a self, tags: &[u32]) -> (usize, Vec<(&'a PathNamed, usize, usize)>) {
+        self.fields.iter().zip(tags).fold(
+            (0, Vec::new()),
+            |(acc_len, mut acc_vec), (field, tag)| {
+                let (len, remote) = field.pb_max_len(*tag);
+                acc_vec.extend(remote);
+                (acc_len + len, acc_vec)
+            },
+        )
+    }
+
+    pub fn as_owned(&mut self) {
+        self.path.as_owned();
+        self.fields.iter_mut().for_each(|f| f.as_owned());
+    }
+
+    pub fn into_owned(mut self) -> Context {
+        self.as_owned();
+        self
+    }
+
+    pub fn as_view(&mut self) {
+        self.path.as_view();
+        self.fields.iter_mut().for_each(|f| f.as_view());
+    }
+
+    pub fn strip_serde(mut self) -> Context {
+        self.fields.iter_mut().for_each(|f| f.serde.clear());
+        self
+    }
+
+    pub fn into_view(mut self) -> Context {
+        self.as_view();
+        self
+    }
+
+    pub fn impl_from_owned(&self) -> ImplFromOwned {
+        ImplFromOwned::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_from_ref(&self) -> ImplFromRef {
+        ImplFromRef::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_view_from_ref(&self) -> ImplViewFromRef<'_> {
+        ImplViewFromRef::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_from_view(&self) -> ImplFromView<'_> {
+        ImplFromView::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_default(&self) -> ImplDefault {
+        ImplDefault::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_init(&self) -> ImplInit<'_> {
+        ImplInit::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_copy_from(&self) -> ImplCopyFrom<'_> {
+        ImplCopyFrom::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_weight(&self) -> ImplWeight {
+        let (weight, remotes) = self.weight();
+        ImplWeight::new(&self.path, weight, remotes)
+    }
+
+    pub fn impl_cbor_weight(&self) -> ImplBinaryWeight<'_> {
+        let (weight, remotes) = self.binary_weight(&utils::CBOR);
+        let shouty = PathNamed::into_shouty_cbor_max_len;
+        ImplBinaryWeight::new(&self.path, weight, remotes, shouty)
+    }
+
+    pub fn impl_msgpack_weight(&self) -> ImplBinaryWeight<'_> {
+        let (weight, remotes) = self.binary_weight(&utils::MSGPACK);
+        let shouty = PathNamed::into_shouty_msgpack_max_len;
+        ImplBinaryWeight::new(&self.path, weight, remotes, shouty)
+    }
+
+    pub fn impl_layout(&self) -> ImplLayout<'_> {
+        ImplLayout::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_view_layout(&self) -> ImplViewLayout<'_> {
+        ImplViewLayout::new(&self.path)
+    }
+
+    pub fn impl_abi_hash(&self) -> ImplAbiHash<'_> {
+        ImplAbiHash::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_protobuf(&self) -> Result<ImplProtobuf<'_>> {
+        let tags = self.pb_tags()?;
+        let (max_len, remotes) = self.pb_max_len(&tags);
+        Ok(ImplProtobuf::new(
+            &self.path,
+            &self.fields,
+            tags,
+            max_len,
+            remotes,
+        ))
+    }
+
+    pub fn impl_chunk(&self) -> ImplChunk<'_> {
+        ImplChunk::new(&self.path, &self.fields, self.rename_all)
+    }
+
+    pub fn partial(&self) -> Partial<'_> {
+        Partial::new(self.tok_vis.as_ref(), &self.path, &self.fields)
+    }
+
+    pub fn impl_merge(&self) -> ImplMerge<'_> {
+        ImplMerge::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_diff(&self) -> ImplDiff<'_> {
+        ImplDiff::new(&self.path, &self.fields, self.rename_all)
+    }
+
+    pub fn impl_pointer(&self) -> ImplPointer<'_> {
+        ImplPointer::new(&self.path, &self.fields)
+    }
+
+    pub fn impl_feed(&self) -> ImplFeed<'_> {
+        ImplFeed::new(&self.path, &self.fields, self.rename_all)
+    }
+
+    pub fn impl_binding(&self) -> ImplBinding<'_> {
+        ImplBinding::new(&self.path)
+    }
+
+    /// The extern "C" functions of the enabled bindings
+    pub fn bindings(&self, naming: &Naming, enabled: &Bindings, table: bool) -> TokenStream {
+        let bindings = if table {
+            vec![
+                ("init", self.binding_init(naming).into_token_stream()),
+                (
+                    "abi_hash",
+                    self.binding_abi_hash(naming).into_token_stream(),
+                ),
+                (
+                    "descriptor",
+                    self.binding_descriptor(naming).into_token_stream(),
+                ),
+                (
+                    "parse_owned",
+                    self.binding_table_parse(naming).into_token_stream(),
+                ),
+                (
+                    "print_owned",
+                    self.binding_table_print(naming).into_token_stream(),
+                ),
+                (
+                    "pb_encode",
+                    self.binding_pb_encode(naming).into_token_stream(),
+                ),
+                (
+                    "pb_decode",
+                    self.binding_pb_decode(naming).into_token_stream(),
+                ),
+            ]
+        } else {
+            vec![
+                ("copy", self.binding_copy(naming).into_token_stream()),
+                ("init", self.binding_init(naming).into_token_stream()),
+                (
+                    "abi_hash",
+                    self.binding_abi_hash(naming).into_token_stream(),
+                ),
+                (
+                    "descriptor",
+                    self.binding_descriptor(naming).into_token_stream(),
+                ),
+                ("parse", self.binding_parse(naming).into_token_stream()),
+                (
+                    "parse_feed",
+                    self.binding_parse_feed(naming).into_token_stream(),
+                ),
+                ("merge", self.binding_merge(naming).into_token_stream()),
+                ("diff", self.binding_diff(naming).into_token_stream()),
+                ("changed", self.binding_changed(naming).into_token_stream()),
+                ("get", self.binding_get(naming).into_token_stream()),
+                ("set", self.binding_set(naming).into_token_stream()),
+                ("print", self.binding_print(naming).into_token_stream()),
+                (
+                    "print_owned",
+                    self.binding_print_owned(naming).into_token_stream(),
+                ),
+                (
+                    "write_owned",
+                    self.binding_write_owned(naming).into_token_stream(),
+                ),
+                (
+                    "print_chunk",
+                    self.binding_print_chunk(naming).into_token_stream(),
+                ),
+                (
+                    "print_view",
+                    self.binding_print_view(naming).into_token_stream(),
+                ),
+                (
+                    "cbor_parse",
+                    self.binding_cbor_parse(naming).into_token_stream(),
+                ),
+                (
+                    "cbor_print",
+                    self.binding_cbor_print(naming).into_token_stream(),
+                ),
+                (
+                    "msgpack_parse",
+                    self.binding_msgpack_parse(naming).into_token_stream(),
+                ),
+                (
+                    "msgpack_print",
+                    self.binding_msgpack_print(naming).into_token_stream(),
+                ),
+                (
+                    "pb_encode",
+                    self.binding_pb_encode(naming).into_token_stream(),
+                ),
+                (
+                    "pb_decode",
+                    self.binding_pb_decode(naming).into_token_stream(),
+                ),
+            ]
+        };
+        bindings
+            .into_iter()
+            .filter(|(name, _)| enabled.has(name))
+            .map(|(_, toks)| toks)
+            .collect()
+    }
+
+    pub fn binding_copy<'a>(&'a self, naming: &'a Naming) -> BindingCopy<'a> {
+        BindingCopy::new(naming, &self.path)
+    }
+
+    pub fn binding_init<'a>(&'a self, naming: &'a Naming) -> BindingDefault<'a> {
+        BindingDefault::new(naming, &self.path)
+    }
+
+    pub fn binding_parse<'a>(&'a self, naming: &'a Naming) -> BindingParse<'a> {
+        BindingParse::new(naming, &self.path)
+    }
+
+    pub fn binding_parse_feed<'a>(&'a self, naming: &'a Naming) -> BindingParseFeed<'a> {
+        BindingParseFeed::new(naming, &self.path)
+    }
+
+    pub fn binding_merge<'a>(&'a self, naming: &'a Naming) -> BindingMerge<'a> {
+        BindingMerge::new(naming, &self.path)
+    }
+
+    pub fn binding_diff<'a>(&'a self, naming: &'a Naming) -> BindingDiff<'a> {
+        BindingDiff::new(naming, &self.path)
+    }
+
+    pub fn binding_descriptor<'a>(&'a self, naming: &'a Naming) -> BindingDescriptor<'a> {
+        BindingDescriptor::new(naming, &self.path, &self.fields, self.rename_all)
+    }
+
+    pub fn binding_abi_hash<'a>(&'a self, naming: &'a Naming) -> BindingAbiHash<'a> {
+        BindingAbiHash::new(naming, &self.path)
+    }
+
+    pub fn binding_changed<'a>(&'a self, naming: &'a Naming) -> BindingChanged<'a> {
+        BindingChanged::new(naming, &self.path)
+    }
+
+    pub fn binding_get<'a>(&'a self, naming: &'a Naming) -> BindingGet<'a> {
+        BindingGet::new(naming, &self.path)
+    }
+
+    pub fn binding_set<'a>(&'a self, naming: &'a Naming) -> BindingSet<'a> {
+        BindingSet::new(naming, &self.path)
+    }
+
+    pub fn binding_table_parse<'a>(&'a self, naming: &'a Naming) -> BindingTableParse<'a> {
+        BindingTableParse::new(naming, &self.path)
+    }
+
+    pub fn binding_table_print<'a>(&'a self, naming: &'a Naming) -> BindingTablePrint<'a> {
+        BindingTablePrint::new(naming, &self.path)
+    }
+
+    pub fn binding_print<'a>(&'a self, naming: &'a Naming) -> BindingPrint<'a> {
+        BindingPrint::new(naming, &self.path)
+    }
+
+    pub fn binding_print_owned<'a>(&'a self, naming: &'a Naming) -> BindingPrintOwned<'a> {
+        BindingPrintOwned::new(naming, &self.path)
+    }
+
+    pub fn binding_write_owned<'a>(&'a self, naming: &'a Naming) -> BindingWriteOwned<'a> {
+        BindingWriteOwned::new(naming, &self.path)
+    }
+
+    pub fn binding_print_chunk<'a>(&'a self, naming: &'a Naming) -> BindingPrintChunk<'a> {
+        BindingPrintChunk::new(naming, &self.path)
+    }
+
+    pub fn binding_print_view<'a>(&'a self, naming: &'a Naming) -> BindingPrintView<'a> {
+        BindingPrintView::new(naming, &self.path)
+    }
+
+    pub fn binding_cbor_parse<'a>(&'a self, naming: &'a Naming) -> BindingCborParse<'a> {
+        BindingCborParse::new(naming, &self.path)
+    }
+
+    pub fn binding_cbor_print<'a>(&'a self, naming: &'a Naming) -> BindingCborPrint<'a> {
+        BindingCborPrint::new(naming, &self.path)
+    }
+
+    pub fn binding_msgpack_parse<'a>(&'a self, naming: &'a Naming) -> BindingMsgpackParse<'a> {
+        BindingMsgpackParse::new(naming, &self.path)
+    }
+
+    pub fn binding_msgpack_print<'a>(&'a self, naming: &'a Naming) -> BindingMsgpackPrint<'a> {
+        BindingMsgpackPrint::new(naming, &self.path)
+    }
+
+    pub fn binding_pb_encode<'a>(&'a self, naming: &'a Naming) -> BindingPbEncode<'a> {
+        BindingPbEncode::new(naming, &self.path)
+    }
+
+    pub fn binding_pb_decode<'a>(&'a self, naming: &'a Naming) -> BindingPbDecode<'a> {
+        BindingPbDecode::new(naming, &self.path)
+    }
+}
+
+impl Parse for Context {
+    fn parse(mut input: ParseStream) -> Result<Self> {
+        syn::Attribute::parse_inner(input)?;
+        let inner;
+        Ok(Context {
+            tok_vis: utils::maybe(Token![pub], &mut input)?,
+            tok_struct: input.parse()?,
+            path: input.parse()?,
+            tok_brace: syn::braced!(inner in input),
+            fields: inner.parse_terminated(Field::parse)?,
+            rename_all: None,
+        })
+    }
+}
+
+impl ToTokens for Context {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        if let Some(vis) = self.tok_vis {
+            vis.to_tokens(toks);
+        }
+        self.tok_struct.to_tokens(toks);
+        self.path.to_tokens(toks);
+        self.tok_brace.surround(toks, |toks| {
+            self.fields.to_tokens(toks);
+        })
+    }
+}
+
+pub struct ImplDefault<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplDefault<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplDefault<'a> {
+        ImplDefault { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplDefault<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.path.split_self_for_impl();
+        let assignment_tokens = self.fields.iter().map(|field| field.assignment_tokens());
+        quote! {
+            impl Default for #owned {
+                fn default() -> #owned {
+                    #owned {
+                        #(#assignment_tokens),*
+                    }
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplInit<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplInit<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplInit<'a> {
+        ImplInit { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplInit<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.path.split_self_for_impl();
+        let dst = quote::format_ident!("self");
+        let init_tokens = self.fields.iter().map(|field| field.init_tokens(&dst));
+        quote! {
+            impl serde_bindgen_core::in_place::Init for #owned {
+                fn init(&mut self) {
+                    #(#init_tokens)*
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplCopyFrom<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplCopyFrom<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplCopyFrom<'a> {
+        ImplCopyFrom { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplCopyFrom<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, owned) = self.path.split_self_for_impl();
+        let (impl_generics, _ty_generics, where_clause) = self.path.split_generics_for_impl();
+        let var = quote::format_ident!("s");
+        let dst = quote::format_ident!("self");
+        let copy_tokens = self
+            .fields
+            .iter()
+            .map(|field| field.copy_tokens(&var, &dst));
+        quote! {
+            impl #impl_generics serde_bindgen_core::in_place::CopyFrom<#borrowed> for #owned #where_clause {
+                fn copy_from(&mut self, s: &#borrowed) {
+                    #(#copy_tokens)*
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplWeight<'a> {
+    pub path: &'a PathNamed,
+    pub weight: usize,
+    pub remotes: Vec<(&'a PathNamed, usize)>,
+}
+
+impl<'a> ImplWeight<'a> {
+    pub fn new(
+        path: &'a PathNamed,
+        weight: usize,
+        remotes: Vec<(&'a PathNamed, usize)>,
+    ) -> ImplWeight<'a> {
+        ImplWeight {
+            path,
+            weight,
+            remotes,
+        }
+    }
+}
+
+impl<'a> ToTokens for ImplWeight<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (mut original, _borrowed, _owned) = self.path.split_self_for_impl();
+        let weight = self.weight;
+        let remotes = self
+            .remotes
+            .iter()
+            .map(|(remote, n)| ((*remote).clone().into_shouty_max_len(), n))
+            .fold(None, |acc, (remote, n)| Some(quote! {#acc + #remote * #n}));
+        original = original.clone().into_shouty_max_len();
+        quote! {
+            pub const #original: usize = #weight #remotes;
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplBinaryWeight<'a> {
+    pub path: &'a PathNamed,
+    pub weight: usize,
+    pub remotes: Vec<(&'a PathNamed, usize)>,
+    /// Names the constant of a struct. IE: FOO_CBOR_MAX_LEN
+    pub shouty: fn(PathNamed) -> PathNamed,
+}
+
+impl<'a> ImplBinaryWeight<'a> {
+    pub fn new(
+        path: &'a PathNamed,
+        weight: usize,
+        remotes: Vec<(&'a PathNamed, usize)>,
+        shouty: fn(PathNamed) -> PathNamed,
+    ) -> ImplBinaryWeight<'a> {
+        ImplBinaryWeight {
+            path,
+            weight,
+            remotes,
+            shouty,
+        }
+    }
+}
+
+impl<'a> ToTokens for ImplBinaryWeight<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let weight = self.weight;
+        let remotes = self.remotes.iter().map(|(remote, n)| {
+            let remote = (self.shouty)((*remote).clone());
+            quote! {+ #remote * #n}
+        });
+        let name = (self.shouty)(self.path.clone());
+        quote! {
+            pub const #name: usize = #weight #(#remotes)*;
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplLayout<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplLayout<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplLayout<'a> {
+        ImplLayout { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplLayout<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.path.split_self_for_impl();
+        let layout = self.path.clone().into_shouty_layout();
+        let n = self.fields.len();
+        let fields = self.fields.iter().map(|field| field.layout_tokens());
+        let offsets = self.fields.iter().enumerate().map(|(i, field)| {
+            let ident = &field.ident;
+            quote! {
+                const _: () = assert!(core::mem::offset_of!(#owned, #ident) == #layout.offsets[#i]);
+            }
+        });
+        quote! {
+            pub const #layout: serde_bindgen_core::layout::Layout<#n> =
+                serde_bindgen_core::layout::Layout::new([#(#fields),*]);
+            const _: () = assert!(core::mem::size_of::<#owned>() == #layout.size);
+            const _: () = assert!(core::mem::align_of::<#owned>() == #layout.align);
+            #(#offsets)*
+        }
+        .to_tokens(toks);
+    }
+}
+
+/// C stores a borrowed struct as its view, as a rust `&str` has no C layout
+pub struct ImplViewLayout<'a> {
+    pub path: &'a PathNamed,
+}
+
+impl<'a> ImplViewLayout<'a> {
+    pub fn new(path: &'a PathNamed) -> ImplViewLayout<'a> {
+        ImplViewLayout { path }
+    }
+}
+
+impl<'a> ToTokens for ImplViewLayout<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, mut borrowed, _owned) = self.path.split_self_for_impl();
+        let view = self.path.clone().into_view();
+        borrowed.strip_generics();
+        quote! {
+            const _: () = assert!(core::mem::size_of::<#borrowed>() == core::mem::size_of::<#view>());
+            const _: () = assert!(core::mem::align_of::<#borrowed>() == core::mem::align_of::<#view>());
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplAbiHash<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplAbiHash<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplAbiHash<'a> {
+        ImplAbiHash { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplAbiHash<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let hash = self.path.clone().into_shouty_abi_hash();
+        let layout = self.path.clone().into_shouty_layout();
+        let fields = self.fields.iter().map(|field| match field.abi() {
+            (abi, None) => quote! {.str(#abi)},
+            (abi, Some(nested)) => {
+                let nested = nested.clone().into_shouty_abi_hash();
+                quote! {.str(#abi).u64(#nested)}
+            }
+        });
+        quote! {
+            pub const #hash: u64 = serde_bindgen_core::layout::AbiHash::new()
+                #(#fields)*
+                .layout(&#layout)
+                .finish();
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplProtobuf<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+    pub tags: Vec<u32>,
+    pub max_len: usize,
+    pub remotes: Vec<(&'a PathNamed, usize, usize)>,
+}
+
+impl<'a> ImplProtobuf<'a> {
+    pub fn new(
+        path: &'a PathNamed,
+        fields: &'a Punctuated<Field, Token![,]>,
+        tags: Vec<u32>,
+        max_len: usize,
+        remotes: Vec<(&'a PathNamed, usize, usize)>,
+    ) -> ImplProtobuf<'a> {
+        ImplProtobuf {
+            path,
+            fields,
+            tags,
+            max_len,
+            remotes,
+        }
+    }
+}
+
+impl<'a> ToTokens for ImplProtobuf<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (original, _borrowed, owned) = self.path.split_self_for_impl();
+        let max_len_ident = original.into_shouty_pb_max_len();
+        let max_len = self.max_len;
+        let remotes = self.remotes.iter().map(|(remote, key, n)| {
+            let remote = (*remote).clone().into_shouty_pb_max_len();
+            quote! {+ serde_bindgen_core::protobuf::delimited_len(#key, #remote) * #n}
+        });
+        let fields = self.fields.iter().zip(&self.tags);
+        let encode = fields
+            .clone()
+            .map(|(field, tag)| field.pb_encode_tokens(*tag));
+        let decode = fields.map(|(field, tag)| field.pb_decode_tokens(*tag));
+        let counts = self
+            .fields
+            .iter()
+            .filter(|field| !field.ty.dims().is_empty())
+            .map(|field| field.pb_count_ident());
+        quote! {
+            pub const #max_len_ident: usize = #max_len #(#remotes)*;
+            impl serde_bindgen_core::protobuf::Message for #owned {
+                fn encode(
+                    &self,
+                    enc: &mut serde_bindgen_core::protobuf::Encoder<'_>,
+                ) -> serde_bindgen_core::protobuf::Result<()> {
+                    #(#encode)*
+                    Ok(())
+                }
+
+                fn decode(
+                    &mut self,
+                    dec: &mut serde_bindgen_core::protobuf::Decoder<'_>,
+                ) -> serde_bindgen_core::protobuf::Result<()> {
+                    #(let mut #counts = 0usize;)*
+                    while let Some((tag, wire)) = dec.key()? {
+                        match tag {
+                            #(#decode)*
+                            _ => dec.skip(wire)?,
+                        }
+                    }
+                    Ok(())
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplFromRef<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplFromRef<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplFromRef<'a> {
+        ImplFromRef { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplFromRef<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, owned) = self.path.split_self_for_impl();
+        let (impl_generics, _ty_generics, where_clause) = self.path.split_generics_for_impl();
+        let var = quote::format_ident!("s");
+        let from_tokens = self
+            .fields
+            .iter()
+            .map(|field| field.from_owned_tokens(&var));
+        quote! {
+            impl #impl_generics From<&#borrowed> for #owned #where_clause {
+                fn from(s: &#borrowed) -> #owned {
+                    #owned {
+                        #(#from_tokens),*
+                    }
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplFromOwned<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplFromOwned<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplFromOwned<'a> {
+        ImplFromOwned { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplFromOwned<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, owned) = self.path.split_self_for_impl();
+        let (impl_generics, _ty_generics, where_clause) = self.path.split_generics_for_impl();
+        let var = quote::format_ident!("s");
+        let from_tokens = self.fields.iter().map(|field| field.from_ref_tokens(&var));
+        let lifetime = borrowed.lifetime();
+        let mut ret = borrowed.clone();
+        ret.strip_generics();
+        quote! {
+            impl #impl_generics From<&#lifetime #owned> for #borrowed #where_clause {
+                fn from(s: &#lifetime #owned) -> #borrowed {
+                    #ret {
+                        #(#from_tokens),*
+                    }
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplViewFromRef<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplViewFromRef<'a> {
+    pub fn new(
+        path: &'a PathNamed,
+        fields: &'a Punctuated<Field, Token![,]>,
+    ) -> ImplViewFromRef<'a> {
+        ImplViewFromRef { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplViewFromRef<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, _owned) = self.path.split_self_for_impl();
+        let (impl_generics, _ty_generics, where_clause) = self.path.split_generics_for_impl();
+        let view = self.path.clone().into_view();
+        let var = quote::format_ident!("s");
+        let view_tokens = self.fields.iter().map(|field| field.to_view_tokens(&var));
+        quote! {
+            impl #impl_generics From<&#borrowed> for #view #where_clause {
+                fn from(s: &#borrowed) -> #view {
+                    #view {
+                        #(#view_tokens),*
+                    }
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplFromView<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplFromView<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplFromView<'a> {
+        ImplFromView { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplFromView<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, _owned) = self.path.split_self_for_impl();
+        let view = self.path.clone().into_view();
+        let var = quote::format_ident!("s");
+        let from_tokens = self
+            .fields
+            .iter()
+            .map(|field| field.ref_from_view_tokens(&var));
+        // The strings borrow from the view
+        let (impl_generics, _ty_generics, where_clause) = self.path.split_generics_for_impl();
+        let (impl_generics, lifetime) = match borrowed.lifetime() {
+            Some(lifetime) => (quote! {#impl_generics}, quote! {#lifetime}),
+            None => (quote! {<'a>}, quote! {'a}),
+        };
+        let mut ret = borrowed.clone();
+        ret.strip_generics();
+        quote! {
+            impl #impl_generics serde_bindgen_core::FromView<#lifetime, #view> for #borrowed #where_clause {
+                unsafe fn from_view(s: &#lifetime #view) -> Result<#borrowed, core::str::Utf8Error> {
+                    Ok(#ret {
+                        #(#from_tokens),*
+                    })
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplBinding<'a> {
+    pub path: &'a PathNamed,
+}
+
+impl<'a> ImplBinding<'a> {
+    pub fn new(path: &'a PathNamed) -> ImplBinding<'a> {
+        ImplBinding { path }
+    }
+}
+
+impl<'a> ToTokens for ImplBinding<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, owned) = self.path.split_self_for_impl();
+        let max_len = self.path.clone().into_shouty_max_len();
+        let generics = match &self.path.generics {
+            Some(generics) => quote! {#generics},
+            None => quote! {<'a>},
+        };
+        quote! {
+            impl serde_bindgen_core::Binding for #owned {
+                type Owned = #owned;
+                type Borrowed #generics = #borrowed;
+                const MAX_LEN: usize = #max_len;
+                fn to_owned(data: &Self::Borrowed<'_>) -> #owned {
+                    From::from(data)
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplChunk<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+    pub rename_all: Option<RenameRule>,
+}
+
+impl<'a> ImplChunk<'a> {
+    pub fn new(
+        path: &'a PathNamed,
+        fields: &'a Punctuated<Field, Token![,]>,
+        rename_all: Option<RenameRule>,
+    ) -> ImplChunk<'a> {
+        ImplChunk {
+            path,
+            fields,
+            rename_all,
+        }
+    }
+}
+
+impl<'a> ToTokens for ImplChunk<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.path.split_self_for_impl();
+        // Every step of the state machine is a match arm. A nested struct or
+        // array is its own step (with its own state machine one level deeper)
+        let mut steps = vec![quote! {c.raw(b"{")?;}];
+        for (i, field) in self.fields.iter().enumerate() {
+            let sep = if i > 0 { "," } else { "" };
+            let key = format!("{}\"{}\":", sep, field.wire_name(self.rename_all));
+            let key = LitByteStr::new(key.as_bytes(), field.ident.span());
+            let name = &field.ident;
+            let value = field
+                .ty
+                .chunk_tokens(&quote! {&self.#name}, &quote! {depth + 1});
+            if field.ty.is_leaf() {
+                steps.push(quote! {c.raw(#key)?; #value?;});
+            } else {
+                steps.push(quote! {c.raw(#key)?;});
+                steps.push(quote! {#value?;});
+            }
+        }
+        steps.push(quote! {c.raw(b"}")?;});
+        let arms = steps.iter().enumerate().map(|(i, step)| {
+            let i = Literal::u32_unsuffixed(i as u32);
+            quote! {#i => {#step}}
+        });
+        quote! {
+            impl serde_bindgen_core::chunk::Chunk for #owned {
+                fn chunk(&self, c: &mut serde_bindgen_core::chunk::Chunker, depth: usize) -> Result<(), serde_bindgen_core::chunk::Stop> {
+                    loop {
+                        match c.cursor(depth)? {
+                            #(#arms)*
+                            _ => return Ok(()),
+                        }
+                        c.next(depth);
+                    }
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct Partial<'a> {
+    pub vis: Option<&'a Token![pub]>,
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> Partial<'a> {
+    pub fn new(
+        vis: Option<&'a Token![pub]>,
+        path: &'a PathNamed,
+        fields: &'a Punctuated<Field, Token![,]>,
+    ) -> Partial<'a> {
+        Partial { vis, path, fields }
+    }
+}
+
+impl<'a> ToTokens for Partial<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let vis = &self.vis;
+        let partial = self.path.clone().into_partial();
+        let fields = self.fields.iter().map(|field| field.partial_tokens());
+        quote! {
+            #vis struct #partial {
+                #(#fields),*
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplMerge<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplMerge<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplMerge<'a> {
+        ImplMerge { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplMerge<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.path.split_self_for_impl();
+        let (impl_generics, _ty_generics, where_clause) = self.path.split_generics_for_impl();
+        let partial = self.path.clone().into_partial();
+        let var = quote::format_ident!("self");
+        let dst = quote::format_ident!("dst");
+        let merge_tokens = self
+            .fields
+            .iter()
+            .map(|field| field.merge_tokens(&var, &dst));
+        quote! {
+            impl #impl_generics #partial #where_clause {
+                /// Copy the fields present in the document into dst
+                pub fn merge(&self, dst: &mut #owned) {
+                    #(#merge_tokens)*
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplDiff<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+    pub rename_all: Option<RenameRule>,
+}
+
+impl<'a> ImplDiff<'a> {
+    pub fn new(
+        path: &'a PathNamed,
+        fields: &'a Punctuated<Field, Token![,]>,
+        rename_all: Option<RenameRule>,
+    ) -> ImplDiff<'a> {
+        ImplDiff {
+            path,
+            fields,
+            rename_all,
+        }
+    }
+}
+
+impl<'a> ToTokens for ImplDiff<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (original, _borrowed, owned) = self.path.split_self_for_impl();
+        if self.fields.len() > 64 {
+            let msg = "#[binding] can only diff structs with up to 64 fields";
+            return quote! {compile_error!(#msg);}.to_tokens(toks);
+        }
+        let changed = self.fields.iter().enumerate().map(|(i, field)| {
+            let name = &field.ident;
+            let differs = field
+                .ty
+                .differs_tokens(&quote! {self.#name}, &quote! {other.#name});
+            let i = Literal::u64_unsuffixed(i as u64);
+            quote! {
+                if #differs {
+                    changed |= 1 << #i;
+                }
+            }
+        });
+        let fields = self.fields.iter().enumerate().map(|(i, field)| {
+            let name = &field.ident;
+            let key = field.wire_name(self.rename_all);
+            let value = field
+                .ty
+                .patch_tokens(&quote! {self.#name}, &quote! {new.#name});
+            let i = Literal::u64_unsuffixed(i as u64);
+            quote! {
+                if changed & (1 << #i) != 0 {
+                    s.serialize_field(#key, #value)?;
+                }
+            }
+        });
+        let name = original.ident.to_string();
+        quote! {
+            impl serde_bindgen_core::diff::Diff for #owned {
+                fn changed(&self, other: &Self) -> u64 {
+                    let mut changed = 0;
+                    #(#changed)*
+                    changed
+                }
+                fn serialize_patch<S: serde_bindgen_core::serde::Serializer>(&self, new: &Self, serializer: S) -> Result<S::Ok, S::Error> {
+                    use serde_bindgen_core::serde::ser::SerializeStruct;
+                    let changed = self.changed(new);
+                    let mut s = serializer.serialize_struct(#name, changed.count_ones() as usize)?;
+                    #(#fields)*
+                    s.end()
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplPointer<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+}
+
+impl<'a> ImplPointer<'a> {
+    pub fn new(path: &'a PathNamed, fields: &'a Punctuated<Field, Token![,]>) -> ImplPointer<'a> {
+        ImplPointer { path, fields }
+    }
+}
+
+impl<'a> ToTokens for ImplPointer<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.path.split_self_for_impl();
+        let gets = self.fields.iter().enumerate().map(|(i, field)| {
+            let name = &field.ident;
+            let get = field.ty.get_tokens(&quote! {self.#name});
+            let i = Literal::u32_unsuffixed(i as u32);
+            quote! {Some((#i, rest)) => #get,}
+        });
+        quote! {
+            impl serde_bindgen_core::pointer::Pointer for #owned {
+                fn get(&self, path: &[u16], bytes: &mut [u8]) -> serde_bindgen_core::pointer::Result<usize> {
+                    match path.split_first() {
+                        None => serde_bindgen_core::pointer::print(self, bytes),
+                        #(#gets)*
+                        _ => Err(serde_bindgen_core::pointer::Error::NotFound),
+                    }
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingDescriptor<'a> {
+    path: &'a PathNamed,
+    fields: &'a Punctuated<Field, Token![,]>,
+    rename_all: Option<RenameRule>,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingDescriptor<'a> {
+    fn new(
+        naming: &'a Naming,
+        path: &'a PathNamed,
+        fields: &'a Punctuated<Field, Token![,]>,
+        rename_all: Option<RenameRule>,
+    ) -> BindingDescriptor<'a> {
+        BindingDescriptor {
+            path,
+            fields,
+            rename_all,
+            naming,
+        }
+    }
+}
+
+impl<'a> ToTokens for BindingDescriptor<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (original, _borrowed, owned) = self.path.split_self_for_impl();
+        // Rust refers to the statics by the names of the struct, so that the
+        // descriptor of a nested struct is found whatever its naming, and "C"
+        // by the symbols of the naming
+        let descriptor = self.path.clone().into_shouty_descriptor();
+        let fields = self.path.clone().into_shouty_fields();
+        let descriptor_symbol = self
+            .naming
+            .symbol("descriptor", Form::Original, self.path)
+            .to_string();
+        let fields_symbol = self
+            .naming
+            .variant_symbol("descriptor", "fields", Form::Original, self.path)
+            .to_string();
+        let name = utils::c_str(&original.ident.to_string(), original.ident.span());
+        let len = self.fields.len();
+        let entries = self
+            .fields
+            .iter()
+            .map(|field| field.descriptor_tokens(&owned, self.rename_all));
+        quote! {
+            #[export_name = #fields_symbol]
+            pub static #fields: [serde_bindgen_core::descriptor::FieldDescriptor; #len] = [
+                #(#entries),*
+            ];
+            #[export_name = #descriptor_symbol]
+            pub static #descriptor: serde_bindgen_core::descriptor::Descriptor = unsafe {
+                serde_bindgen_core::descriptor::Descriptor::new(
+                    #name,
+                    core::mem::size_of::<#owned>(),
+                    &#fields as *const serde_bindgen_core::descriptor::FieldDescriptor,
+                    #len,
+                )
+            };
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct ImplFeed<'a> {
+    pub path: &'a PathNamed,
+    pub fields: &'a Punctuated<Field, Token![,]>,
+    pub rename_all: Option<RenameRule>,
+}
+
+impl<'a> ImplFeed<'a> {
+    pub fn new(
+        path: &'a PathNamed,
+        fields: &'a Punctuated<Field, Token![,]>,
+        rename_all: Option<RenameRule>,
+    ) -> ImplFeed<'a> {
+        ImplFeed {
+            path,
+            fields,
+            rename_all,
+        }
+    }
+}
+
+impl<'a> ToTokens for ImplFeed<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.path.split_self_for_impl();
+        let keys = self.fields.iter().enumerate().map(|(i, field)| {
+            let key = field.wire_name(self.rename_all);
+            let key = LitByteStr::new(key.as_bytes(), field.ident.span());
+            let i = Literal::u32_unsuffixed(i as u32);
+            quote! {#key => Some(#i),}
+        });
+        let steps = self.fields.iter().enumerate().filter_map(|(i, field)| {
+            let name = &field.ident;
+            let step = field.ty.step_tokens(&quote! {self.#name})?;
+            let i = Literal::u32_unsuffixed(i as u32);
+            Some(quote! {Some((#i, rest)) => #step,})
+        });
+        let slots = self.fields.iter().enumerate().map(|(i, field)| {
+            let name = &field.ident;
+            let slot = field.ty.feed_tokens(&quote! {self.#name});
+            let i = Literal::u32_unsuffixed(i as u32);
+            quote! {Some((#i, rest)) => #slot,}
+        });
+        quote! {
+            impl serde_bindgen_core::feed::Feed for #owned {
+                fn step(&self, path: &[u16], token: &[u8]) -> Option<u16> {
+                    match path.split_first() {
+                        None => match token {
+                            #(#keys)*
+                            _ => None,
+                        },
+                        #(#steps)*
+                        _ => None,
+                    }
+                }
+                fn slot(&mut self, path: &[u16]) -> serde_bindgen_core::feed::Slot<'_> {
+                    match path.split_first() {
+                        None => serde_bindgen_core::feed::Slot::Object,
+                        #(#slots)*
+                        _ => serde_bindgen_core::feed::Slot::Skip,
+                    }
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingDefault<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingDefault<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingDefault<'a> {
+        BindingDefault { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingDefault<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let name_fn = self.naming.symbol("init", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub extern "C" fn #name_fn #impl_generics(dst: &mut #owned)  {
+                serde_bindgen_core::in_place::Init::init(dst);
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingCopy<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingCopy<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingCopy<'a> {
+        BindingCopy { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingCopy<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let name_fn = self.naming.symbol("copy", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub extern "C" fn #name_fn #impl_generics(dst: &mut #owned, src: &#borrowed)  {
+                serde_bindgen_core::in_place::CopyFrom::copy_from(dst, src);
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingParse<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingParse<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingParse<'a> {
+        BindingParse { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingParse<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, _owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let name_fn = self.naming.symbol("parse", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(dst: &mut #borrowed, bytes: *const u8, len: usize) -> i32 {
+                let slice = core::slice::from_raw_parts(bytes, len);
+                match serde_bindgen_core::serde_json_core::from_slice(&slice) {
+                    Ok((item, len))=> {
+                        *dst = item;
+                        len as i32
+                    },
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingMerge<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingMerge<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingMerge<'a> {
+        BindingMerge { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingMerge<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let partial = self.ident.clone().into_partial();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let name_fn = self.naming.symbol("merge", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(dst: &mut #owned, bytes: *const u8, len: usize) -> i32 {
+                let slice = core::slice::from_raw_parts(bytes, len);
+                match serde_bindgen_core::serde_json_core::from_slice::<#partial>(&slice) {
+                    Ok((item, len)) => {
+                        item.merge(dst);
+                        len as i32
+                    },
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingDiff<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingDiff<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingDiff<'a> {
+        BindingDiff { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingDiff<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self.naming.symbol("diff", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(old: &#lifetime #owned, new: &#lifetime #owned, bytes: *mut u8, len: &#lifetime mut usize) -> i32 {
+                let mut slice = core::slice::from_raw_parts_mut(bytes, *len);
+                let patch = serde_bindgen_core::diff::Patch { old, new };
+                match serde_bindgen_core::serde_json_core::to_slice(&patch, &mut slice) {
+                    Ok(l)=> {
+                        *len = l;
+                        0
+                    },
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingAbiHash<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingAbiHash<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingAbiHash<'a> {
+        BindingAbiHash { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingAbiHash<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let name_fn = self.naming.symbol("abi_hash", Form::Original, self.ident);
+        let hash = self.ident.clone().into_shouty_abi_hash();
+        quote! {
+            #[no_mangle]
+            pub extern "C" fn #name_fn() -> u64 {
+                #hash
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingChanged<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingChanged<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingChanged<'a> {
+        BindingChanged { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingChanged<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self.naming.symbol("changed", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub extern "C" fn #name_fn #impl_generics(old: &#lifetime #owned, new: &#lifetime #owned) -> u64 {
+                serde_bindgen_core::diff::Diff::changed(old, new)
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingGet<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingGet<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingGet<'a> {
+        BindingGet { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingGet<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self.naming.symbol("get", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(data: &#lifetime #owned, pointer: *const u8, pointer_len: usize, bytes: *mut u8, len: &#lifetime mut usize) -> i32 {
+                let pointer = core::slice::from_raw_parts(pointer, pointer_len);
+                let slice = core::slice::from_raw_parts_mut(bytes, *len);
+                match serde_bindgen_core::pointer::get(data, pointer, slice) {
+                    Ok(l)=> {
+                        *len = l;
+                        0
+                    },
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingSet<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingSet<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingSet<'a> {
+        BindingSet { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingSet<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self.naming.symbol("set", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(dst: &#lifetime mut #owned, pointer: *const u8, pointer_len: usize, bytes: *const u8, len: usize) -> i32 {
+                let pointer = core::slice::from_raw_parts(pointer, pointer_len);
+                let slice = core::slice::from_raw_parts(bytes, len);
+                match serde_bindgen_core::pointer::set(dst, pointer, slice) {
+                    Ok(()) => 0,
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingTableParse<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingTableParse<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingTableParse<'a> {
+        BindingTableParse { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingTableParse<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let descriptor = self.ident.clone().into_shouty_descriptor();
+        let name_fn = self.naming.symbol("parse", Form::Owned, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn(dst: &mut #owned, bytes: *const u8, len: usize) -> i32 {
+                let slice = core::slice::from_raw_parts(bytes, len);
+                let dst = dst as *mut #owned as *mut u8;
+                match unsafe { serde_bindgen_core::table::parse(&#descriptor, dst, slice) } {
+                    Some(len) => len as i32,
+                    None => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingTablePrint<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingTablePrint<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingTablePrint<'a> {
+        BindingTablePrint { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingTablePrint<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let descriptor = self.ident.clone().into_shouty_descriptor();
+        let name_fn = self.naming.symbol("print", Form::Owned, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn(data: &#owned, bytes: *mut u8, len: &mut usize) -> i32 {
+                let slice = core::slice::from_raw_parts_mut(bytes, *len);
+                let data = data as *const #owned as *const u8;
+                match unsafe { serde_bindgen_core::table::print(&#descriptor, data, slice) } {
+                    Ok(l)=> {
+                        *len = l;
+                        0
+                    },
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingPrint<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingPrint<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingPrint<'a> {
+        BindingPrint { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingPrint<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, _owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let name_fn = self.naming.symbol("print", Form::Borrowed, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(data: &#borrowed, bytes: *mut u8, len: &mut usize) -> i32 {
+                let mut slice = core::slice::from_raw_parts_mut(bytes, *len);
+                match serde_bindgen_core::serde_json_core::to_slice(data, &mut slice) {
+                    Ok(l)=> {
+                        *len = l;
+                        0
+                    },
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingPrintOwned<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingPrintOwned<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingPrintOwned<'a> {
+        BindingPrintOwned { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingPrintOwned<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self.naming.symbol("print", Form::Owned, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(data: &#lifetime #owned, bytes: *mut u8, len: &#lifetime mut usize) -> i32 {
+                let mut slice = core::slice::from_raw_parts_mut(bytes, *len);
+                match serde_bindgen_core::serde_json_core::to_slice(data, &mut slice) {
+                    Ok(l)=> {
+                        *len = l;
+                        0
+                    },
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingWriteOwned<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingWriteOwned<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingWriteOwned<'a> {
+        BindingWriteOwned { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingWriteOwned<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self.naming.symbol("write", Form::Owned, self.ident);
+        quote! {
+            #[no_mangle]
+            pub extern "C" fn #name_fn #impl_generics(data: &#lifetime #owned, write: serde_bindgen_core::writer::WriteFn, ctx: *mut core::ffi::c_void) -> i32 {
+                match serde_bindgen_core::writer::to_callback(data, write, ctx) {
+                    Ok(_) => 0,
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingPrintChunk<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingPrintChunk<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingPrintChunk<'a> {
+        BindingPrintChunk { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingPrintChunk<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self
+            .naming
+            .variant_symbol("print", "chunk", Form::Owned, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(state: &#lifetime mut serde_bindgen_core::chunk::ChunkState, data: &#lifetime #owned, bytes: *mut u8, len: usize) -> i32 {
+                let slice = core::slice::from_raw_parts_mut(bytes, len);
+                match serde_bindgen_core::chunk::print_chunk(data, state, slice) {
+                    Ok(l) => l as i32,
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingParseFeed<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingParseFeed<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingParseFeed<'a> {
+        BindingParseFeed { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingParseFeed<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self
+            .naming
+            .variant_symbol("parse", "feed", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(state: &#lifetime mut serde_bindgen_core::feed::FeedState, dst: &#lifetime mut #owned, bytes: *const u8, len: usize) -> i32 {
+                let slice = core::slice::from_raw_parts(bytes, len);
+                match state.feed(dst, slice) {
+                    serde_bindgen_core::feed::Status::NeedMore => 0,
+                    serde_bindgen_core::feed::Status::Complete(l) => l as i32,
+                    serde_bindgen_core::feed::Status::Error => -1,
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingPrintView<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingPrintView<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingPrintView<'a> {
+        BindingPrintView { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingPrintView<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, borrowed, _owned) = self.ident.split_self_for_impl();
+        let (impl_generics, _, _) = self.ident.split_generics_for_impl();
+        let view = self.ident.clone().into_view();
+        let lifetime = self.ident.lifetime();
+        let name_fn = self.naming.symbol("print", Form::View, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn #impl_generics(data: &#lifetime #view, bytes: *mut u8, len: &#lifetime mut usize) -> i32 {
+                let mut slice = core::slice::from_raw_parts_mut(bytes, *len);
+                // The caller vouches for the strings of the view
+                let data: #borrowed = match unsafe { serde_bindgen_core::FromView::from_view(data) } {
+                    Ok(data) => data,
+                    Err(_) => return -1,
+                };
+                match serde_bindgen_core::serde_json_core::to_slice(&data, &mut slice) {
+                    Ok(l)=> {
+                        *len = l;
+                        0
+                    },
+                    Err(_) => -1
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingPbEncode<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingPbEncode<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingPbEncode<'a> {
+        BindingPbEncode { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingPbEncode<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let name_fn = self.naming.symbol("pb_encode", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn(src: &#owned, bytes: *mut u8, len: &mut usize) -> i32 {
+                let slice = core::slice::from_raw_parts_mut(bytes, *len);
+                match serde_bindgen_core::protobuf::encode(src, slice) {
+                    Ok(l) => {
+                        *len = l;
+                        0
+                    }
+                    Err(_) => -1,
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingPbDecode<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingPbDecode<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingPbDecode<'a> {
+        BindingPbDecode { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingPbDecode<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let name_fn = self.naming.symbol("pb_decode", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn(dst: &mut #owned, bytes: *const u8, len: usize) -> i32 {
+                let slice = core::slice::from_raw_parts(bytes, len);
+                // absent fields take their defaults, and dst is left alone on error
+                let mut decoded = <#owned as Default>::default();
+                match serde_bindgen_core::protobuf::decode(&mut decoded, slice) {
+                    Ok(()) => {
+                        *dst = decoded;
+                        0
+                    }
+                    Err(_) => -1,
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingCborParse<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingCborParse<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingCborParse<'a> {
+        BindingCborParse { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingCborParse<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let name_fn = self.naming.symbol("cbor_parse", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn(dst: &mut #owned, bytes: *const u8, len: usize) -> i32 {
+                let slice = core::slice::from_raw_parts(bytes, len);
+                // the whole input is one item, decoded into dst field by field
+                match serde_bindgen_core::cbor::from_slice_in_place(dst, slice) {
+                    Ok(read) if read == len => len as i32,
+                    _ => -1,
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingCborPrint<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingCborPrint<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingCborPrint<'a> {
+        BindingCborPrint { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingCborPrint<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let name_fn = self.naming.symbol("cbor_print", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn(src: &#owned, bytes: *mut u8, len: &mut usize) -> i32 {
+                let slice = core::slice::from_raw_parts_mut(bytes, *len);
+                match serde_bindgen_core::cbor::to_slice(src, slice) {
+                    Ok(l) => {
+                        *len = l;
+                        0
+                    }
+                    Err(_) => -1,
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingMsgpackParse<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingMsgpackParse<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingMsgpackParse<'a> {
+        BindingMsgpackParse { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingMsgpackParse<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let name_fn = self
+            .naming
+            .symbol("msgpack_parse", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn(dst: &mut #owned, bytes: *const u8, len: usize) -> i32 {
+                let slice = core::slice::from_raw_parts(bytes, len);
+                // the whole input is one value, decoded into dst field by field
+                match serde_bindgen_core::msgpack::from_slice_in_place(dst, slice) {
+                    Ok(read) if read == len => len as i32,
+                    _ => -1,
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
+
+pub struct BindingMsgpackPrint<'a> {
+    ident: &'a PathNamed,
+    naming: &'a Naming,
+}
+
+impl<'a> BindingMsgpackPrint<'a> {
+    fn new(naming: &'a Naming, ident: &'a PathNamed) -> BindingMsgpackPrint<'a> {
+        BindingMsgpackPrint { ident, naming }
+    }
+}
+
+impl<'a> ToTokens for BindingMsgpackPrint<'a> {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        let (_original, _borrowed, owned) = self.ident.split_self_for_impl();
+        let name_fn = self
+            .naming
+            .symbol("msgpack_print", Form::Original, self.ident);
+        quote! {
+            #[no_mangle]
+            pub unsafe extern "C" fn #name_fn(src: &#owned, bytes: *mut u8, len: &mut usize) -> i32 {
+                let slice = core::slice::from_raw_parts_mut(bytes, *len);
+                match serde_bindgen_core::msgpack::to_slice(src, slice) {
+                    Ok(l) => {
+                        *len = l;
+                        0
+                    }
+                    Err(_) => -1,
+                }
+            }
+        }
+        .to_tokens(toks);
+    }
+}
@@ -0,0 +1,205 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Render a C++ header which wraps the owned `#[binding]` structs of a crate
+//! in classes. Used by the sbc-gen binary.
+//!
+//! The classes sit on top of the C header (which the C++ header includes),
+//! one per binding in a namespace named by its prefix. Each holds the owned
+//! struct by value, parses and prints with the extern functions, throws
+//! `sbc::error` when they fail, and reads string fields as
+//! `std::string_view`. `merge` updates the struct with the keys of a JSON
+//! object and keeps the other fields, through the merge binding (or the
+//! table parse binding, which does the same).
+
+use std::fmt::Write;
+
+use syn::parse::{Error, Result};
+
+use crate::attributes::Features;
+use crate::field::FieldType;
+use crate::header::Binding;
+use crate::naming::Form;
+
+/// Methods of every class, which a string field accessor may not shadow
+const METHODS: [&str; 3] = ["merge", "print", "raw"];
+
+/// The error and helpers shared by every class
+const PRELUDE: &str = r#"namespace sbc {
+
+/// Thrown when a binding returns an error code
+class error : public std::runtime_error {
+  public:
+    error(const char *what, int32_t code) : std::runtime_error(what), code_(code) {}
+    int32_t code() const noexcept { return code_; }
+
+  private:
+    int32_t code_;
+};
+
+namespace detail {
+
+/// A string field up to its NUL terminator
+inline std::string_view view(const char *s, size_t len) {
+    size_t n = 0;
+    while (n < len && s[n]) {
+        n++;
+    }
+    return std::string_view(s, n);
+}
+
+} // namespace detail
+} // namespace sbc
+"#;
+
+/// Render the header for the bindings of the features, which includes the C
+/// header at include
+pub fn render(
+    found: &[Binding],
+    guard: &str,
+    include: &str,
+    features: &Features,
+) -> Result<String> {
+    let mut out = String::new();
+    writeln!(out, "/* Generated by sbc-gen, do not edit */").unwrap();
+    writeln!(out, "#ifndef {guard}\n#define {guard}\n").unwrap();
+    writeln!(
+        out,
+        "#include <stdexcept>\n#include <string>\n#include <string_view>\n"
+    )
+    .unwrap();
+    writeln!(out, "#include \"{}\"\n", include).unwrap();
+    out.push_str(PRELUDE);
+    for binding in found {
+        out.push('\n');
+        class(&mut out, binding, features)?;
+    }
+    writeln!(out, "\n#endif /* {guard} */").unwrap();
+    Ok(out)
+}
+
+fn class(out: &mut String, binding: &Binding, features: &Features) -> Result<()> {
+    let table = binding.attrs.table()?;
+    let enabled = binding.attrs.bindings(features)?;
+    let path = &binding.ctx.path;
+    let name = &path.ident;
+    let raw = binding.owned();
+    let max_len = path.clone().into_shouty_max_len().ident;
+
+    writeln!(out, "namespace {} {{\n", binding.naming.prefix).unwrap();
+    for line in &binding.docs {
+        writeln!(out, "/// {}", line).unwrap();
+    }
+    writeln!(out, "class {} {{\n  public:", name).unwrap();
+    match enabled.has("init") {
+        true => {
+            let init = binding.naming.symbol("init", Form::Original, path);
+            writeln!(out, "    {}() {{ {}(&raw_); }}", name, init).unwrap();
+        }
+        false => writeln!(out, "    {}() : raw_{{}} {{}}", name).unwrap(),
+    }
+    writeln!(
+        out,
+        "    explicit {}(const {} &raw) : raw_(raw) {{}}\n",
+        name, raw
+    )
+    .unwrap();
+
+    // merge json into this struct
+    let merge = match table {
+        true if enabled.has("parse_owned") => {
+            Some(binding.naming.symbol("parse", Form::Owned, path))
+        }
+        false if enabled.has("merge") => Some(binding.naming.symbol("merge", Form::Original, path)),
+        _ => None,
+    };
+    if let Some(merge) = merge {
+        writeln!(out, "    void merge(std::string_view json) {{").unwrap();
+        writeln!(
+            out,
+            "        int32_t code = {}(&raw_, reinterpret_cast<const uint8_t *>(json.data()), json.size());",
+            merge
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        if (code < 0) {{\n            throw sbc::error(\"{}: merge failed\", code);\n        }}\n    }}\n",
+            name
+        )
+        .unwrap();
+    }
+
+    // print this struct
+    if enabled.has("print_owned") {
+        let print = binding.naming.symbol("print", Form::Owned, path);
+        writeln!(out, "    std::string print() const {{").unwrap();
+        writeln!(out, "        std::string json({}, '\\0');", max_len).unwrap();
+        writeln!(out, "        size_t len = json.size();").unwrap();
+        writeln!(
+            out,
+            "        int32_t code = {}(&raw_, reinterpret_cast<uint8_t *>(&json[0]), &len);",
+            print
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        if (code < 0) {{\n            throw sbc::error(\"{}: print failed\", code);\n        }}",
+            name
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        json.resize(len);\n        return json;\n    }}\n"
+        )
+        .unwrap();
+    }
+
+    // read string fields, indexed through any array dimensions
+    for field in &binding.ctx.fields {
+        if let FieldType::RefStr(_) = field.ty.innermost() {
+            if METHODS.contains(&field.ident.to_string().as_str()) {
+                let msg = format!("string field `{}` clashes with a C++ method", field.ident);
+                return Err(Error::new(field.ident.span(), msg));
+            }
+            let dims = field.ty.dims();
+            let params = (0..dims.len())
+                .map(|i| format!("size_t i{}", i))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let index: String = (0..dims.len()).map(|i| format!("[i{}]", i)).collect();
+            let ident = &field.ident;
+            writeln!(
+                out,
+                "    std::string_view {ident}({params}) const {{\n        return sbc::detail::view(raw_.{ident}{index}, sizeof(raw_.{ident}{index}));\n    }}\n"
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(out, "    {} &raw() noexcept {{ return raw_; }}", raw).unwrap();
+    writeln!(
+        out,
+        "    const {} &raw() const noexcept {{ return raw_; }}",
+        raw
+    )
+    .unwrap();
+    writeln!(out, "\n  private:\n    {} raw_;\n}};", raw).unwrap();
+    writeln!(out, "\n}} // namespace {}", binding.naming.prefix).unwrap();
+    Ok(())
+}
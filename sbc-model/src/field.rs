@@ -0,0 +1,978 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! TODO make trait "FieldType".
+//!      A "FieldType" can
+//!      - represent itself in context of assignment (default impl)
+//!      - represent itself in context of copy (from impl)
+//!      - represent itself in context of in place init and copy
+//!      - represent itself in context of partial copy (merge impl)
+//!      - can tokanize itself as a standard version
+//!      - can tokenize itself as an "owned" version
+//!      - can tokenize itself as a "partial"
+
+// syn::
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::token::Bracket;
+use syn::Ident;
+use syn::LitInt;
+use syn::Token;
+
+// quote::
+use quote::quote;
+use quote::ToTokens;
+
+use proc_macro2::TokenStream;
+
+// super:
+use super::attributes::{Attribute, Attributes, DefaultLit, RenameRule};
+use super::path::PathNamed;
+use super::utils::{self, BinaryFormat};
+
+#[derive(Clone)]
+pub struct Field {
+    pub ident: Ident,
+    pub tok_vis: Option<Token![pub]>,
+    pub tok_col: Token![:],
+    pub ty: FieldType,
+    pub attributes: Attributes,
+    pub serde: Vec<TokenStream>,
+}
+
+impl Field {
+    pub fn as_owned(&mut self) {
+        // Once owned a string is a [u8; N], so tell serde it is still a string
+        if self.ty.is_str() {
+            self.serde
+                .push(quote! {#[serde(with = "serde_bindgen_core::owned_str")]});
+        }
+        self.ty.as_owned(&self.attributes);
+    }
+
+    pub fn as_view(&mut self) {
+        self.ty.as_view();
+    }
+
+    pub fn assignment_tokens(&self) -> TokenStream {
+        let name = &self.ident;
+        let init = self.attributes.seek_default();
+        let assignment = self.ty.assignment_tokens(&init);
+        quote! {#name: #assignment}
+    }
+
+    /// Write the default of this field into dst in place
+    pub fn init_tokens(&self, dst: &Ident) -> TokenStream {
+        let name = &self.ident;
+        let init = self.attributes.seek_default();
+        self.ty.init_tokens(&quote! {#dst.#name}, &init)
+    }
+
+    /// Copy this field of the borrowed var into dst in place
+    pub fn copy_tokens(&self, var: &Ident, dst: &Ident) -> TokenStream {
+        let name = &self.ident;
+        self.ty
+            .copy_tokens(&quote! {#dst.#name}, &quote! {#var.#name})
+    }
+
+    pub fn from_owned_tokens(&self, var: &Ident) -> TokenStream {
+        let name = &self.ident;
+        let expr = quote! {#var.#name};
+        let assignment = self.ty.from_owned_tokens(&parse_quote! {#expr});
+        quote! {#name: #assignment}
+    }
+
+    pub fn from_ref_tokens(&self, var: &Ident) -> TokenStream {
+        let name = &self.ident;
+        let expr = quote! {#var.#name};
+        let assignment = self.ty.from_ref_tokens(&parse_quote! {#expr});
+        quote! {#name: #assignment}
+    }
+
+    pub fn to_view_tokens(&self, var: &Ident) -> TokenStream {
+        let name = &self.ident;
+        let expr = quote! {#var.#name};
+        let assignment = self.ty.to_view_tokens(&parse_quote! {#expr});
+        quote! {#name: #assignment}
+    }
+
+    pub fn ref_from_view_tokens(&self, var: &Ident) -> TokenStream {
+        let name = &self.ident;
+        let expr = quote! {#var.#name};
+        let assignment = self.ty.ref_from_view_tokens(&parse_quote! {#expr});
+        quote! {#name: #assignment}
+    }
+
+    /// The field of the partial struct, where every field is optional
+    pub fn partial_tokens(&self) -> TokenStream {
+        let mut ty = self.ty.clone();
+        ty.as_partial();
+        let borrow = if ty.has_lifetime() {
+            quote! {#[serde(borrow)]}
+        } else {
+            quote! {}
+        };
+        let vis = &self.tok_vis;
+        let name = &self.ident;
+        quote! {#borrow #vis #name: Option<#ty>}
+    }
+
+    pub fn merge_tokens(&self, var: &Ident, dst: &Ident) -> TokenStream {
+        let name = &self.ident;
+        match &self.ty {
+            FieldType::Struct(_) => quote! {
+                if let Some(v) = &#var.#name {
+                    v.merge(&mut #dst.#name);
+                }
+            },
+            ty => {
+                let assignment = ty.from_owned_tokens(&quote! {(*v)});
+                quote! {
+                    if let Some(v) = &#var.#name {
+                        #dst.#name = #assignment;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The entry of this field in the descriptor table of owned
+    pub fn descriptor_tokens(
+        &self,
+        owned: &PathNamed,
+        rename_all: Option<RenameRule>,
+    ) -> TokenStream {
+        let ident = &self.ident;
+        let name = utils::c_str(&self.ident.to_string(), self.ident.span());
+        let wire_name = utils::c_str(&self.wire_name(rename_all), self.ident.span());
+        let kind = self.ty.kind_tokens();
+        let count = self.ty.count();
+        let dims = self.ty.dims();
+        let rank = dims.len();
+        let capacity = match self.ty.is_str() {
+            true => self.attributes.seek_len(),
+            false => 0,
+        };
+        let nested = self.ty.nested_tokens();
+        quote! {
+            unsafe {
+                serde_bindgen_core::descriptor::FieldDescriptor::new(
+                    #name,
+                    #wire_name,
+                    #kind,
+                    core::mem::offset_of!(#owned, #ident),
+                    #count,
+                    &[#(#dims),*] as *const [usize; #rank] as *const usize,
+                    #rank,
+                    #capacity,
+                    #nested,
+                )
+            }
+        }
+    }
+
+    /// The key serde uses for this field
+    pub fn wire_name(&self, rename_all: Option<RenameRule>) -> String {
+        let name = self.ident.to_string();
+        match rename_all {
+            Some(rule) => rule.apply_to_field(&name),
+            None => name,
+        }
+    }
+
+    /// The name and C type of the field fed to the ABI hash. A nested struct
+    /// is written as `_` and returned, so its own hash can stand in for it
+    pub fn abi(&self) -> (String, Option<&PathNamed>) {
+        let (ty, nested) = self.ty.abi(&self.attributes);
+        (format!("{}:{}", self.ident, ty), nested)
+    }
+
+    /// The size and alignment C gives the field, as a const expression
+    pub fn layout_tokens(&self) -> TokenStream {
+        self.ty.layout_tokens(&self.attributes)
+    }
+
+    /// The most bytes the field takes as a map entry of a binary format,
+    /// keyed by its serde name, and the nested struct whose own max stands in
+    /// for part of it
+    pub fn binary_weight(
+        &self,
+        format: &BinaryFormat,
+        rename_all: Option<RenameRule>,
+    ) -> (usize, Option<(&PathNamed, usize)>) {
+        let key = self.wire_name(rename_all).len();
+        let (size, remote) = self.ty.binary_weight(format, &self.attributes);
+        ((format.str_head)(key) + key + size, remote)
+    }
+
+    /// Statement writing the field to a protobuf encoder as field number tag
+    pub fn pb_encode_tokens(&self, tag: u32) -> TokenStream {
+        let ident = &self.ident;
+        let value = match self.ty.dims().len() {
+            0 => match self.ty {
+                FieldType::Primative(_) => quote! {self.#ident},
+                _ => quote! {&self.#ident},
+            },
+            _ => self.ty.flat_tokens(quote! {self.#ident}, false),
+        };
+        let method = self.ty.pb_method();
+        quote! {enc.#method(#tag, #value)?;}
+    }
+
+    /// Match arm reading field number tag from a protobuf decoder. Arrays
+    /// count the elements read so far in `count_<field>`
+    pub fn pb_decode_tokens(&self, tag: u32) -> TokenStream {
+        let ident = &self.ident;
+        let method = self.ty.pb_method();
+        match self.ty.dims().len() {
+            0 => quote! {#tag => dec.#method(wire, &mut self.#ident)?,},
+            _ => {
+                let count = self.pb_count_ident();
+                let slice = self.ty.flat_tokens(quote! {self.#ident}, true);
+                quote! {#tag => dec.#method(wire, #slice, &mut #count)?,}
+            }
+        }
+    }
+
+    /// The local counting the elements of an array read by the decoder
+    pub fn pb_count_ident(&self) -> Ident {
+        quote::format_ident!("count_{}", self.ident)
+    }
+
+    /// The most bytes the field encodes to as field number tag. A nested
+    /// struct is returned with the length of its key and its count, so its
+    /// own max can stand in for it
+    pub fn pb_max_len(&self, tag: u32) -> (usize, Option<(&PathNamed, usize, usize)>) {
+        let key = utils::varint_len((tag as u64) << 3);
+        let count = self.ty.count();
+        let packed = !self.ty.dims().is_empty();
+        match self.ty.innermost() {
+            FieldType::Struct(p) => (0, Some((p, key, count))),
+            FieldType::RefStr(_) => {
+                let len = self.attributes.seek_len().saturating_sub(1); // no NUL
+                (count * (key + utils::varint_len(len as u64) + len), None)
+            }
+            ty if packed => {
+                let len = count * ty.pb_varint_max();
+                (key + utils::varint_len(len as u64) + len, None)
+            }
+            ty => (key + ty.pb_varint_max(), None),
+        }
+    }
+
+    pub fn weight<'a>(&'a self) -> (usize, Option<(&'a PathNamed, usize)>) {
+        // TODO - this assumption if field is decorated with a rename attribute
+        //        therefore we should check attributes for an alias and use alias
+        //        for weight calculation if it exists
+        let wrapper_len = self.ident.to_string().len() + 3; // sizeof("%s": )
+        let (size, remote) = self.ty.weight(&self.attributes);
+        (size + wrapper_len, remote)
+    }
+}
+
+impl Parse for Field {
+    fn parse(mut input: ParseStream) -> Result<Self> {
+        let mut attributes = Vec::new();
+        while input.peek(Token![#]) {
+            attributes.push(input.parse::<Attribute>()?);
+        }
+        Ok(Field {
+            tok_vis: utils::maybe(Token![pub], &mut input)?,
+            ident: input.parse()?,
+            tok_col: input.parse()?,
+            ty: input.parse()?,
+            attributes: attributes.into(),
+            serde: Vec::new(),
+        })
+    }
+}
+
+impl ToTokens for Field {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        // We consume all the /// data: attributes but leave other doc comments
+        self.attributes
+            .0
+            .iter()
+            .filter_map(|attr| attr.ignore())
+            .for_each(|meta| meta.to_tokens(toks));
+        self.serde.iter().for_each(|attr| attr.to_tokens(toks));
+
+        if let Some(vis) = self.tok_vis {
+            vis.to_tokens(toks);
+        }
+        self.ident.to_tokens(toks);
+        self.tok_col.to_tokens(toks);
+        self.ty.to_tokens(toks);
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(PartialEq, Debug))]
+pub enum FieldType {
+    RefStr(FieldTypeRef),
+    Struct(PathNamed),
+    Primative(Ident),
+    Array(FieldTypeArray),
+}
+
+impl FieldType {
+    /// The size and alignment C gives the field, given the size and alignment
+    /// of the nested structs
+    pub fn layout<F>(&self, attrs: &Attributes, nested: &F) -> Option<(usize, usize)>
+    where
+        F: Fn(&PathNamed) -> Option<(usize, usize)>,
+    {
+        match self {
+            FieldType::Primative(p) if p == "bool" || p == "u8" || p == "i8" => Some((1, 1)),
+            FieldType::Primative(p) if p == "u16" || p == "i16" => Some((2, 2)),
+            FieldType::Primative(p) if p == "u32" || p == "i32" => Some((4, 4)),
+            FieldType::RefStr(_) => Some((attrs.seek_len(), 1)),
+            FieldType::Struct(p) => nested(p),
+            FieldType::Array(arr) => arr
+                .ty
+                .layout(attrs, nested)
+                .map(|(size, align)| (size * arr.len(), align)),
+            _ => None,
+        }
+    }
+
+    /// The C type of the field as the ABI hash sees it. IE: `[str8;2]`
+    pub fn abi<'a>(&'a self, attrs: &Attributes) -> (String, Option<&'a PathNamed>) {
+        match self {
+            FieldType::Primative(p) => (p.to_string(), None),
+            FieldType::RefStr(_) => (format!("str{}", attrs.seek_len()), None),
+            FieldType::Struct(p) => ("_".to_string(), Some(p)),
+            FieldType::Array(arr) => {
+                let (ty, nested) = arr.ty.abi(attrs);
+                (format!("[{};{}]", ty, arr.len()), nested)
+            }
+        }
+    }
+
+    /// The size and alignment C gives the field, as a const expression. The
+    /// layout of a nested struct is read from its FOO_LAYOUT constant
+    pub fn layout_tokens(&self, attrs: &Attributes) -> TokenStream {
+        match self {
+            FieldType::Struct(p) => {
+                let layout = p.clone().into_shouty_layout();
+                quote! {(#layout.size, #layout.align)}
+            }
+            FieldType::Array(arr) => {
+                let n = arr.len();
+                let inner = arr.ty.layout_tokens(attrs);
+                quote! {{
+                    let (size, align) = #inner;
+                    (size * #n, align)
+                }}
+            }
+            ty => {
+                let (size, align) = ty
+                    .layout(attrs, &|_| None)
+                    .unwrap_or_else(|| unimplemented!());
+                quote! {(#size, #align)}
+            }
+        }
+    }
+
+    pub fn weight<'a>(&'a self, attrs: &Attributes) -> (usize, Option<(&'a PathNamed, usize)>) {
+        match self {
+            FieldType::Primative(p) if p == "bool" => (5, None), // sizeof(false)
+            FieldType::Primative(p) if p == "u8" => (3, None),   // sizeof(255)
+            FieldType::Primative(p) if p == "i8" => (4, None),   // sizeof(-128)
+            FieldType::Primative(p) if p == "u16" => (5, None),  // sizeof(65536)
+            FieldType::Primative(p) if p == "i16" => (6, None),  // sizeof(-32768)
+            FieldType::Primative(p) if p == "u32" => (10, None), // sizeof(4294967296)
+            FieldType::Primative(p) if p == "i32" => (11, None), // sizeof(-2147483648)
+            FieldType::RefStr(_) => (attrs.seek_len() + 2, None), // sizeof("%s")
+            FieldType::Struct(p) => (0, Some((p, 1))),
+            FieldType::Array(arr) => {
+                let n = arr.n.base10_digits().parse().unwrap_or(0);
+                let wrap = if n > 0 { n - 1 + 2 } else { 2 }; // 1 per comma except last (n-1), + []
+                match arr.ty.weight(attrs) {
+                    (size, Some((remote, count))) => (size * n + wrap, Some((remote, count * n))),
+                    (size, None) => (size * n + wrap, None),
+                }
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    pub fn binary_weight(
+        &self,
+        format: &BinaryFormat,
+        attrs: &Attributes,
+    ) -> (usize, Option<(&PathNamed, usize)>) {
+        match self {
+            FieldType::Primative(p) if p == "bool" => (1, None),
+            FieldType::Primative(p) if p == "u8" || p == "i8" => (2, None),
+            FieldType::Primative(p) if p == "u16" || p == "i16" => (3, None),
+            FieldType::Primative(p) if p == "u32" || p == "i32" => (5, None),
+            FieldType::RefStr(_) => {
+                let len = attrs.seek_len().saturating_sub(1); // no NUL
+                ((format.str_head)(len) + len, None)
+            }
+            FieldType::Struct(p) => (0, Some((p, 1))),
+            FieldType::Array(arr) => {
+                let n = arr.len();
+                let head = (format.array_head)(n);
+                match arr.ty.binary_weight(format, attrs) {
+                    (size, Some((remote, count))) => (head + size * n, Some((remote, count * n))),
+                    (size, None) => (head + size * n, None),
+                }
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    pub fn is_str(&self) -> bool {
+        match self {
+            FieldType::RefStr(_) => true,
+            FieldType::Array(FieldTypeArray { ty, .. }) => ty.is_str(),
+            _ => false,
+        }
+    }
+
+    pub fn as_owned(&mut self, attr: &Attributes) {
+        match self {
+            FieldType::RefStr(FieldTypeRef { ident, .. }) => {
+                let span = ident.span().clone();
+                let len = attr
+                    .seek_len_lit()
+                    .unwrap_or_else(|| LitInt::new("0", span.clone()));
+                *self = FieldType::Array(parse_quote! {[u8;#len]});
+            }
+            FieldType::Array(FieldTypeArray { ty, .. }) => {
+                ty.as_owned(attr);
+            }
+            FieldType::Struct(p) => {
+                p.as_owned();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn as_view(&mut self) {
+        match self {
+            FieldType::RefStr(_) => {
+                *self = FieldType::Struct(parse_quote! {serde_bindgen_core::StrView});
+            }
+            FieldType::Array(FieldTypeArray { ty, .. }) => {
+                ty.as_view();
+            }
+            FieldType::Struct(p) => {
+                p.as_view();
+            }
+            _ => {}
+        }
+    }
+
+    /// Nested structs merge field by field, everything else is replaced
+    pub fn as_partial(&mut self) {
+        if let FieldType::Struct(p) = self {
+            p.as_partial();
+        }
+    }
+
+    pub fn has_lifetime(&self) -> bool {
+        match self {
+            FieldType::RefStr(_) => true,
+            FieldType::Struct(p) => p.lifetime().is_some(),
+            FieldType::Primative(_) => false,
+            FieldType::Array(FieldTypeArray { ty, .. }) => ty.has_lifetime(),
+        }
+    }
+
+    pub fn assignment_tokens(&self, expr: &Option<&DefaultLit>) -> TokenStream {
+        match (expr, &self) {
+            (Some(e), FieldType::RefStr(_)) => {
+                quote! {serde_bindgen_core::SafeCopy::safe_copy(&#e)}
+            }
+            (None, FieldType::RefStr(_)) => quote! {serde_bindgen_core::SafeCopy::safe_copy(&"")},
+            (_, FieldType::Struct(_)) => quote! {Default::default()},
+            (None, FieldType::Primative(i)) if i == "bool" => quote! {false},
+            (None, FieldType::Primative(_)) => quote! {0},
+            (Some(expr), FieldType::Primative(_)) => quote! {#expr},
+            (Some(expr), FieldType::Array(_)) if expr.is_array() => quote! {#expr},
+            (_, FieldType::Array(a)) => a.repeat(a.ty.assignment_tokens(&expr)),
+        }
+    }
+
+    /// Assign the default to the place expression, element by element
+    pub fn init_tokens(&self, place: &TokenStream, expr: &Option<&DefaultLit>) -> TokenStream {
+        match (expr, &self) {
+            (Some(e), FieldType::RefStr(_)) => {
+                quote! {serde_bindgen_core::in_place::copy_str(&mut #place, #e);}
+            }
+            (None, FieldType::RefStr(_)) => {
+                quote! {serde_bindgen_core::in_place::copy_str(&mut #place, "");}
+            }
+            (_, FieldType::Struct(_)) => {
+                quote! {serde_bindgen_core::in_place::Init::init(&mut #place);}
+            }
+            (Some(expr), FieldType::Array(_)) if expr.is_array() => quote! {#place = #expr;},
+            (_, FieldType::Array(a)) => {
+                let each = a.ty.init_tokens(&quote! {(*item)}, expr);
+                quote! {
+                    for item in #place.iter_mut() {
+                        #each
+                    }
+                }
+            }
+            (_, ty) => {
+                let assignment = ty.assignment_tokens(expr);
+                quote! {#place = #assignment;}
+            }
+        }
+    }
+
+    /// Copy the borrowed src expression to the place expression, element by element
+    pub fn copy_tokens(&self, place: &TokenStream, src: &TokenStream) -> TokenStream {
+        match &self {
+            FieldType::RefStr(_) => {
+                quote! {serde_bindgen_core::in_place::copy_str(&mut #place, #src);}
+            }
+            FieldType::Struct(_) => {
+                quote! {serde_bindgen_core::in_place::CopyFrom::copy_from(&mut #place, &#src);}
+            }
+            FieldType::Primative(_) => quote! {#place = #src;},
+            FieldType::Array(a) => {
+                let each = a.ty.copy_tokens(&quote! {(*item)}, &quote! {(*src)});
+                quote! {
+                    for (item, src) in #place.iter_mut().zip(#src.iter()) {
+                        #each
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn from_owned_tokens(&self, expr: &TokenStream) -> TokenStream {
+        match &self {
+            FieldType::RefStr(_) => quote! {serde_bindgen_core::SafeCopy::safe_copy(&#expr)},
+            FieldType::Struct(_) => quote! {From::from(&#expr)},
+            FieldType::Primative(_) => quote! {#expr},
+            FieldType::Array(a) => a.surround(|i| {
+                let expr = quote! {#expr[#i]};
+                a.ty.from_owned_tokens(&expr)
+            }),
+        }
+    }
+
+    pub fn from_ref_tokens(&self, expr: &TokenStream) -> TokenStream {
+        match &self {
+            FieldType::RefStr(_) => {
+                quote! {core::str::from_utf8(#expr.as_slice()).unwrap_or("").trim_end_matches(char::from(0))}
+            }
+            FieldType::Struct(_) => quote! {From::from(&#expr)},
+            FieldType::Primative(_) => quote! {#expr},
+            FieldType::Array(a) => a.surround(|i| {
+                let expr = quote! {#expr[#i]};
+                a.ty.from_ref_tokens(&expr)
+            }),
+        }
+    }
+
+    pub fn to_view_tokens(&self, expr: &TokenStream) -> TokenStream {
+        match &self {
+            FieldType::RefStr(_) => quote! {serde_bindgen_core::StrView::from(#expr)},
+            FieldType::Struct(_) => quote! {From::from(&#expr)},
+            FieldType::Primative(_) => quote! {#expr},
+            FieldType::Array(a) => a.surround(|i| {
+                let expr = quote! {#expr[#i]};
+                a.ty.to_view_tokens(&expr)
+            }),
+        }
+    }
+
+    pub fn ref_from_view_tokens(&self, expr: &TokenStream) -> TokenStream {
+        match self.innermost() {
+            FieldType::Primative(_) => quote! {#expr},
+            _ => quote! {serde_bindgen_core::FromView::from_view(&#expr)?},
+        }
+    }
+}
+
+impl FieldType {
+    /// A value printed in one piece by the chunked printer
+    pub fn is_leaf(&self) -> bool {
+        matches!(self, FieldType::RefStr(_) | FieldType::Primative(_))
+    }
+
+    /// Print the value behind the reference expr with the chunked printer
+    pub fn chunk_tokens(&self, expr: &TokenStream, depth: &TokenStream) -> TokenStream {
+        match &self {
+            FieldType::RefStr(_) => quote! {c.leaf(&serde_bindgen_core::owned_str::AsStr(#expr))},
+            FieldType::Struct(_) => {
+                quote! {serde_bindgen_core::chunk::Chunk::chunk(#expr, c, #depth)}
+            }
+            FieldType::Primative(_) => quote! {c.leaf(#expr)},
+            FieldType::Array(a) => {
+                let each = a.ty.chunk_tokens(&quote! {item}, &quote! {depth});
+                quote! {serde_bindgen_core::chunk::array(c, #depth, #expr, |c, item, depth| #each)}
+            }
+        }
+    }
+
+    /// The descriptor kind of the innermost type
+    pub fn kind_tokens(&self) -> TokenStream {
+        match &self {
+            FieldType::RefStr(_) => quote! {serde_bindgen_core::descriptor::Kind::Str},
+            FieldType::Struct(_) => quote! {serde_bindgen_core::descriptor::Kind::Struct},
+            FieldType::Primative(i) => {
+                let kind = Ident::new(&utils::capitalize(&i.to_string()), i.span());
+                quote! {serde_bindgen_core::descriptor::Kind::#kind}
+            }
+            FieldType::Array(a) => a.ty.kind_tokens(),
+        }
+    }
+
+    /// Number of elements, all dimensions of an array multiplied
+    pub fn count(&self) -> usize {
+        match &self {
+            FieldType::Array(a) => a.len() * a.ty.count(),
+            _ => 1,
+        }
+    }
+
+    /// The dimensions of an array, outermost first
+    pub fn dims(&self) -> Vec<usize> {
+        match &self {
+            FieldType::Array(a) => {
+                let mut dims = vec![a.len()];
+                dims.extend(a.ty.dims());
+                dims
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The element type of an array, through every dimension
+    pub fn innermost(&self) -> &FieldType {
+        match &self {
+            FieldType::Array(a) => a.ty.innermost(),
+            ty => ty,
+        }
+    }
+
+    /// The Encoder and Decoder method for the field, by its innermost type
+    pub fn pb_method(&self) -> Ident {
+        let (one, many) = match self.innermost() {
+            FieldType::RefStr(_) => ("string", "strings"),
+            FieldType::Struct(_) => ("message", "messages"),
+            _ => ("scalar", "packed"),
+        };
+        let method = if self.dims().is_empty() { one } else { many };
+        Ident::new(method, proc_macro2::Span::call_site())
+    }
+
+    /// The most bytes a number takes as a varint (zigzag when signed)
+    pub fn pb_varint_max(&self) -> usize {
+        match self {
+            FieldType::Primative(p) if p == "bool" => 1,
+            FieldType::Primative(p) if p == "u8" || p == "i8" => 2,
+            FieldType::Primative(p) if p == "u16" || p == "i16" => 3,
+            FieldType::Primative(p) if p == "u32" || p == "i32" => 5,
+            _ => unimplemented!(),
+        }
+    }
+
+    /// An array as a slice of its innermost elements, in row major order
+    pub fn flat_tokens(&self, expr: TokenStream, mutable: bool) -> TokenStream {
+        let flatten = match mutable {
+            true => quote! {.as_flattened_mut()},
+            false => quote! {.as_flattened()},
+        };
+        match self.dims().len() {
+            1 if mutable => quote! {&mut #expr},
+            1 => quote! {&#expr},
+            n => {
+                let flatten = (1..n).map(|_| flatten.clone());
+                quote! {#expr #(#flatten)*}
+            }
+        }
+    }
+
+    /// Pointer to the descriptor of the innermost type, if it is a struct
+    pub fn nested_tokens(&self) -> TokenStream {
+        match &self {
+            FieldType::Struct(p) => {
+                let descriptor = p.clone().into_shouty_descriptor();
+                quote! {&#descriptor as *const serde_bindgen_core::descriptor::Descriptor}
+            }
+            FieldType::Array(a) => a.ty.nested_tokens(),
+            _ => quote! {core::ptr::null()},
+        }
+    }
+
+    /// True when the values behind a and b differ
+    pub fn differs_tokens(&self, a: &TokenStream, b: &TokenStream) -> TokenStream {
+        match &self {
+            FieldType::RefStr(_) => quote! {
+                serde_bindgen_core::owned_str::as_str(&#a) != serde_bindgen_core::owned_str::as_str(&#b)
+            },
+            FieldType::Struct(_) => quote! {serde_bindgen_core::diff::Diff::changed(&#a, &#b) != 0},
+            FieldType::Primative(_) => quote! {#a != #b},
+            FieldType::Array(arr) => {
+                let each = arr.ty.differs_tokens(&quote! {(*a)}, &quote! {(*b)});
+                quote! {#a.iter().zip(#b.iter()).any(|(a, b)| #each)}
+            }
+        }
+    }
+
+    /// The value printed in a patch for a changed field
+    pub fn patch_tokens(&self, old: &TokenStream, new: &TokenStream) -> TokenStream {
+        match &self {
+            FieldType::Struct(_) => {
+                quote! {&serde_bindgen_core::diff::Patch { old: &#old, new: &#new }}
+            }
+            ty if ty.is_str() => quote! {&serde_bindgen_core::owned_str::AsStr(&#new)},
+            _ => quote! {&#new},
+        }
+    }
+
+    /// Print the value at path rest below expr
+    pub fn get_tokens(&self, expr: &TokenStream) -> TokenStream {
+        let whole = if self.is_str() {
+            quote! {serde_bindgen_core::pointer::print(&serde_bindgen_core::owned_str::AsStr(&#expr), bytes)}
+        } else {
+            quote! {serde_bindgen_core::pointer::print(&#expr, bytes)}
+        };
+        match &self {
+            FieldType::Struct(_) => {
+                quote! {serde_bindgen_core::pointer::Pointer::get(&#expr, rest, bytes)}
+            }
+            FieldType::Array(a) => {
+                let each = a.ty.get_tokens(&quote! {(*item)});
+                quote! {
+                    match rest.split_first() {
+                        None => #whole,
+                        Some((i, rest)) => match #expr.get(*i as usize) {
+                            Some(item) => #each,
+                            None => Err(serde_bindgen_core::pointer::Error::NotFound),
+                        },
+                    }
+                }
+            }
+            _ => quote! {
+                match rest {
+                    [] => #whole,
+                    _ => Err(serde_bindgen_core::pointer::Error::NotFound),
+                }
+            },
+        }
+    }
+
+    /// The index of the child named token at path rest below expr. None when
+    /// the value has no children
+    pub fn step_tokens(&self, expr: &TokenStream) -> Option<TokenStream> {
+        match &self {
+            FieldType::Struct(_) => {
+                Some(quote! {serde_bindgen_core::feed::Feed::step(&#expr, rest, token)})
+            }
+            FieldType::Array(a) => {
+                let n = &a.n;
+                let each = match a.ty.step_tokens(&quote! {(*item)}) {
+                    Some(each) => quote! {
+                        Some((i, rest)) => match #expr.get(*i as usize) {
+                            Some(item) => #each,
+                            None => None,
+                        },
+                    },
+                    None => quote! {Some(_) => None,},
+                };
+                Some(quote! {
+                    match rest.split_first() {
+                        None => serde_bindgen_core::feed::index(token, #n),
+                        #each
+                    }
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The feed slot at path rest below the place expr
+    pub fn feed_tokens(&self, expr: &TokenStream) -> TokenStream {
+        match &self {
+            FieldType::RefStr(_) => quote! {
+                match rest {
+                    [] => serde_bindgen_core::feed::Slot::Str(&mut #expr),
+                    _ => serde_bindgen_core::feed::Slot::Skip,
+                }
+            },
+            FieldType::Struct(_) => quote! {serde_bindgen_core::feed::Feed::slot(&mut #expr, rest)},
+            FieldType::Primative(_) => quote! {
+                match rest {
+                    [] => serde_bindgen_core::feed::Slot::from(&mut #expr),
+                    _ => serde_bindgen_core::feed::Slot::Skip,
+                }
+            },
+            FieldType::Array(a) => {
+                let n = &a.n;
+                let each = a.ty.feed_tokens(&quote! {(*item)});
+                quote! {
+                    match rest.split_first() {
+                        None => serde_bindgen_core::feed::Slot::Array(#n),
+                        Some((i, rest)) => match #expr.get_mut(*i as usize) {
+                            Some(item) => #each,
+                            None => serde_bindgen_core::feed::Slot::Skip,
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Parse for FieldType {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![&]) && input.peek2(syn::Lifetime) {
+            Ok(FieldType::RefStr(input.parse()?))
+        } else if input.peek(Token![::]) || input.peek(Ident) && input.peek2(Token![::]) {
+            Ok(FieldType::Struct(input.parse()?))
+        } else if input.peek(Ident) {
+            let path: PathNamed = input.parse()?;
+            if path.is_primative() {
+                Ok(FieldType::Primative(path.ident))
+            } else {
+                Ok(FieldType::Struct(path))
+            }
+        } else if input.peek(Bracket) {
+            Ok(FieldType::Array(input.parse()?))
+        } else {
+            let err = Error::new(input.span(), "Unsupported field type");
+            Err(err)
+        }
+    }
+}
+
+impl ToTokens for FieldType {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        match self {
+            FieldType::RefStr(t) => t.to_tokens(toks),
+            FieldType::Struct(t) => t.to_tokens(toks),
+            FieldType::Primative(t) => t.to_tokens(toks),
+            FieldType::Array(t) => t.to_tokens(toks),
+        }
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(PartialEq, Debug))]
+pub struct FieldTypeRef {
+    pub amp: Token![&],
+    pub lifetime: syn::Lifetime,
+    pub ident: Ident,
+}
+impl Parse for FieldTypeRef {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let amp: Token![&] = input.parse()?;
+        let lifetime: syn::Lifetime = input.parse()?;
+        let ident = input.parse()?;
+        if ident == "str" {
+            Ok(FieldTypeRef {
+                amp,
+                lifetime,
+                ident,
+            })
+        } else {
+            let err = Error::new(input.span(), "Can only have str reference");
+            Err(err)
+        }
+    }
+}
+
+impl ToTokens for FieldTypeRef {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        self.amp.to_tokens(toks);
+        self.lifetime.to_tokens(toks);
+        self.ident.to_tokens(toks);
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(PartialEq, Debug))]
+pub struct FieldTypeArray {
+    bracket: Bracket,
+    ty: Box<FieldType>,
+    semi: Token![;],
+    n: LitInt,
+}
+
+/// Longer arrays are built with a loop rather than one expression per element
+const UNROLL_MAX: usize = 16;
+
+impl FieldTypeArray {
+    fn len(&self) -> usize {
+        self.n.base10_digits().parse().unwrap_or(0)
+    }
+
+    /// Build the array from the expression for each index
+    pub fn surround<F: FnMut(TokenStream) -> TokenStream>(&self, mut func: F) -> TokenStream {
+        let n = self.len();
+        if n > UNROLL_MAX {
+            // Name the index after the depth, so nested arrays do not shadow it
+            let i = quote::format_ident!("i{}", self.ty.dims().len());
+            let each = func(quote! {#i});
+            return quote! {core::array::from_fn(|#i| #each)};
+        }
+        let mut toks = TokenStream::new();
+        let _bracket = self.bracket.surround(&mut toks, |toks| {
+            let mut punc = Punctuated::<TokenStream, Token![,]>::new();
+            for i in 0..n {
+                let i = LitInt::new(&i.to_string(), self.n.span()).token();
+                punc.push(func(quote! {#i}))
+            }
+            punc.to_tokens(toks);
+        });
+        toks
+    }
+
+    /// Build the array from the same expression for every element
+    pub fn repeat(&self, each: TokenStream) -> TokenStream {
+        if self.len() > UNROLL_MAX {
+            quote! {core::array::from_fn(|_| #each)}
+        } else {
+            self.surround(|_| each.clone())
+        }
+    }
+}
+
+impl Parse for FieldTypeArray {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let inner;
+        Ok(FieldTypeArray {
+            bracket: syn::bracketed!(inner in input),
+            ty: inner.parse()?,
+            semi: inner.parse()?,
+            n: inner.parse()?,
+        })
+    }
+}
+
+impl ToTokens for FieldTypeArray {
+    fn to_tokens(&self, toks: &mut TokenStream) {
+        self.bracket.surround(toks, |toks| {
+            self.ty.to_tokens(toks);
+            self.semi.to_tokens(toks);
+            self.n.to_tokens(toks);
+        });
+    }
+}
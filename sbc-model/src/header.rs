@@ -0,0 +1,726 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Render a C header for the `#[binding]` structs of a crate. Used by the
+//! sbc-gen binary.
+//!
+//! The header declares the owned structs (in dependency order), the MAX_LEN
+//! constants and a prototype for every extern function the macro generates.
+//! The prototypes are read back from the macro's own output, so they always
+//! agree with the container options. Each owned struct is followed by
+//! `_Static_assert`s of its size and field offsets, the numbers `FOO_LAYOUT`
+//! asserts on the rust side, and its `FOO_ABI_HASH` is defined for comparing
+//! with the library at run time.
+//!
+//! The views are defined on `StrView` in the same way. Their layout depends
+//! on the width of a pointer, so the asserts pick the number of a 32 or a 64
+//! bit target. A rust `&str` has no C layout, so a borrowed struct is storage
+//! the size of its view, which the binding asserts on the rust side, for C to
+//! hand to parse. The parser states the prototypes name are defined as the
+//! runtime lays them out, the private `FeedState` as a sized byte array.
+//! `FOO_CBOR_MAX_LEN` and `FOO_MSGPACK_MAX_LEN` are defined like MAX_LEN,
+//! but `FOO_PB_MAX_LEN` is worked out here, as C has no way to size a varint.
+//! It is only defined with the protobuf feature, like the Rust constant.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+use quote::ToTokens;
+use syn::parse::{Error, Result};
+use syn::spanned::Spanned;
+
+use crate::attributes::{Attributes, ContainerAttributes, Features, RenameRule};
+use crate::context::Context;
+use crate::field::{Field, FieldType};
+use crate::naming::Naming;
+use crate::path::PathNamed;
+use crate::utils;
+
+/// A struct decorated with `#[binding]`
+pub struct Binding {
+    pub attrs: ContainerAttributes,
+    pub ctx: Context,
+    pub naming: Naming,
+    /// The doc comment of the struct, one entry per line
+    pub docs: Vec<String>,
+}
+
+impl Binding {
+    /// The name C uses for the owned struct
+    pub fn owned(&self) -> String {
+        match &self.naming.owned {
+            Some(owned) => owned.to_string(),
+            None => self.ctx.path.clone().into_owned().ident.to_string(),
+        }
+    }
+
+    /// The name C uses for the borrowed struct
+    pub fn borrowed(&self) -> String {
+        match &self.naming.borrowed {
+            Some(borrowed) => borrowed.to_string(),
+            None => self.ctx.path.clone().into_borrowed().ident.to_string(),
+        }
+    }
+}
+
+/// Collect the `#[binding]` structs of items, including inline modules
+pub fn scan(items: &[syn::Item], found: &mut Vec<Binding>) -> Result<()> {
+    for item in items {
+        match item {
+            syn::Item::Struct(item) => {
+                let attr = item.attrs.iter().find(|attr| {
+                    attr.path
+                        .segments
+                        .last()
+                        .is_some_and(|seg| seg.ident == "binding")
+                });
+                if let Some(attr) = attr {
+                    let attrs = match attr.tokens.is_empty() {
+                        true => syn::parse2(proc_macro2::TokenStream::new())?,
+                        false => attr.parse_args::<ContainerAttributes>()?,
+                    };
+                    let docs = item
+                        .attrs
+                        .iter()
+                        .filter_map(|attr| match attr.parse_meta() {
+                            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                                path,
+                                lit: syn::Lit::Str(doc),
+                                ..
+                            })) if path.is_ident("doc") => Some(doc.value().trim().to_string()),
+                            _ => None,
+                        })
+                        .collect();
+                    let mut item = item.clone();
+                    item.attrs.clear();
+                    let mut ctx: Context = syn::parse2(item.into_token_stream())?;
+                    ctx.rename_all = attrs
+                        .seek_val("rename_all")
+                        .and_then(|lit| RenameRule::parse(&lit.value()));
+                    let naming = Naming::from_container(&attrs)?;
+                    found.push(Binding {
+                        attrs,
+                        ctx,
+                        naming,
+                        docs,
+                    });
+                }
+            }
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => scan(items, found)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Render the header for the bindings of the features, guarded by the macro
+/// guard
+pub fn render(found: &[Binding], guard: &str, features: &Features) -> Result<String> {
+    // C has a single namespace, so the structs of every module share it
+    for (n, binding) in found.iter().enumerate() {
+        let ident = &binding.ctx.path.ident;
+        if found[..n].iter().any(|b| b.ctx.path.ident == *ident) {
+            let msg = format!("{} is declared more than once", ident);
+            return Err(Error::new(ident.span(), msg));
+        }
+    }
+
+    // The C name of each owned struct by the name of its original struct
+    let owned: HashMap<String, String> = found
+        .iter()
+        .map(|binding| (binding.ctx.path.ident.to_string(), binding.owned()))
+        .collect();
+
+    // The C names of the types prototypes may use
+    let mut types: HashMap<String, String> = HashMap::new();
+    for binding in found {
+        let (_original, borrowed, owned) = binding.ctx.path.split_self_for_impl();
+        types.insert(owned.ident.to_string(), binding.owned());
+        types.insert(borrowed.ident.to_string(), binding.borrowed());
+    }
+
+    // The layout of each owned struct by the name of its original struct
+    let sorted = order(found)?;
+    let layouts = layouts(&sorted)?;
+    let pb_max_lens = match features.has("protobuf") {
+        true => pb_max_lens(&sorted)?,
+        false => HashMap::new(),
+    };
+
+    let mut named = BTreeSet::new();
+    let mut prototypes = String::new();
+    for binding in found {
+        let table = binding.attrs.table()?;
+        let enabled = binding.attrs.bindings(features)?;
+        let file: syn::File = syn::parse2(binding.ctx.bindings(&binding.naming, &enabled, table))?;
+        for item in file.items {
+            if let syn::Item::Fn(f) = item {
+                writeln!(prototypes, "{};", prototype(&f.sig, &types, &mut named)?).unwrap();
+            }
+        }
+    }
+
+    // The views and borrowed structs of the serde mode
+    let mut serde = Vec::new();
+    for binding in &sorted {
+        if !binding.attrs.table()? {
+            serde.push(*binding);
+        }
+    }
+    let views = |pointer| view_layouts(&serde, pointer);
+    let (views32, views64) = (views(4)?, views(8)?);
+
+    // Anything else the prototypes name comes with the runtime
+    let mut runtime = Vec::new();
+    for name in &named {
+        let declared = owned.values().any(|v| v == name)
+            || serde
+                .iter()
+                .any(|b| b.borrowed() == *name || view(b) == *name);
+        if !declared {
+            match RUNTIME.iter().find(|(rust, _)| rust == name) {
+                Some((_, definition)) => runtime.push(*definition),
+                None => {
+                    let msg = format!("{} has no C definition", name);
+                    return Err(Error::new(proc_macro2::Span::call_site(), msg));
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(out, "/* Generated by sbc-gen, do not edit */").unwrap();
+    writeln!(out, "#ifndef {guard}\n#define {guard}\n").unwrap();
+    writeln!(
+        out,
+        "#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n"
+    )
+    .unwrap();
+    writeln!(out, "#ifdef __cplusplus\nextern \"C\" {{\n#endif\n").unwrap();
+    writeln!(
+        out,
+        "#if defined(__cplusplus) && !defined(_Static_assert)\n#define _Static_assert static_assert\n#endif\n"
+    )
+    .unwrap();
+    for binding in found {
+        let (len, remotes) = binding.ctx.weight();
+        define_max_len(
+            &mut out,
+            binding,
+            len,
+            remotes,
+            PathNamed::into_shouty_max_len,
+        );
+    }
+    for binding in found {
+        let (len, remotes) = binding.ctx.binary_weight(&utils::CBOR);
+        let shouty = PathNamed::into_shouty_cbor_max_len;
+        define_max_len(&mut out, binding, len, remotes, shouty);
+    }
+    for binding in found {
+        let (len, remotes) = binding.ctx.binary_weight(&utils::MSGPACK);
+        let shouty = PathNamed::into_shouty_msgpack_max_len;
+        define_max_len(&mut out, binding, len, remotes, shouty);
+    }
+    for binding in found {
+        let name = binding.ctx.path.clone().into_shouty_pb_max_len().ident;
+        if let Some(len) = pb_max_lens.get(&binding.ctx.path.ident.to_string()) {
+            writeln!(out, "#define {} {}", name, len).unwrap();
+        }
+    }
+    for binding in found {
+        let name = binding.ctx.path.clone().into_shouty_abi_hash().ident;
+        let hash = layouts[&binding.ctx.path.ident.to_string()].abi_hash;
+        writeln!(out, "#define {} 0x{:016x}ULL", name, hash).unwrap();
+    }
+    out.push('\n');
+    for definition in runtime {
+        writeln!(out, "{}\n", definition).unwrap();
+    }
+    if !serde.is_empty() {
+        writeln!(out, "{}\n", STR_VIEW).unwrap();
+    }
+    for binding in &sorted {
+        let declarations = binding
+            .ctx
+            .fields
+            .iter()
+            .map(|field| declaration(field, &owned, false))
+            .collect::<Result<Vec<String>>>()?;
+
+        // The same layout the binding asserts on the rust side
+        let layout = &layouts[&binding.ctx.path.ident.to_string()];
+        let offsets = layout.offsets.iter().map(|offset| offset.to_string());
+        let size = layout.size.to_string();
+        define_struct(
+            &mut out,
+            &binding.owned(),
+            binding,
+            declarations,
+            size,
+            offsets,
+        );
+    }
+    for binding in &serde {
+        let declarations = binding
+            .ctx
+            .fields
+            .iter()
+            .map(|field| declaration(field, &owned, true))
+            .collect::<Result<Vec<String>>>()?;
+        let name = view(binding);
+        let (layout32, layout64) = (&views32[&name], &views64[&name]);
+        let offsets = layout32
+            .offsets
+            .iter()
+            .zip(&layout64.offsets)
+            .map(|(a, b)| by_width(*a, *b));
+        let size = by_width(layout32.size, layout64.size);
+        define_struct(&mut out, &name, binding, declarations, size, offsets);
+    }
+    for binding in &serde {
+        let name = binding.borrowed();
+        let view = view(binding);
+        writeln!(
+            out,
+            "typedef struct {name} {{\n    {view} opaque;\n}} {name};\n"
+        )
+        .unwrap();
+    }
+    out.push_str(&prototypes);
+    writeln!(
+        out,
+        "\n#ifdef __cplusplus\n}}\n#endif\n\n#endif /* {guard} */"
+    )
+    .unwrap();
+    Ok(out)
+}
+
+/// Define a max len constant of a struct as C sees it, summing the constants
+/// of its nested structs
+fn define_max_len<F>(
+    out: &mut String,
+    binding: &Binding,
+    len: usize,
+    remotes: Vec<(&PathNamed, usize)>,
+    shouty: F,
+) where
+    F: Fn(PathNamed) -> PathNamed,
+{
+    let name = shouty(binding.ctx.path.clone()).ident;
+    let remotes: String = remotes
+        .iter()
+        .map(|(remote, n)| format!(" + {} * {}", shouty((*remote).clone()).ident, n))
+        .collect();
+    match remotes.is_empty() {
+        true => writeln!(out, "#define {} {}", name, len).unwrap(),
+        false => writeln!(out, "#define {} ({}{})", name, len, remotes).unwrap(),
+    }
+}
+
+/// The doc comment of a field, one entry per line. The `sbc:` options are not
+/// part of it
+pub fn docs(attrs: &Attributes) -> Vec<String> {
+    attrs
+        .0
+        .iter()
+        .filter_map(|attr| attr.ignore())
+        .map(|doc| doc.inner.value().trim().to_string())
+        .collect()
+}
+
+/// Define a struct of the field declarations, followed by asserts of its size
+/// and field offsets
+fn define_struct<I>(
+    out: &mut String,
+    name: &str,
+    binding: &Binding,
+    declarations: Vec<String>,
+    size: String,
+    offsets: I,
+) where
+    I: Iterator<Item = String>,
+{
+    writeln!(out, "typedef struct {} {{", name).unwrap();
+    for declaration in declarations {
+        writeln!(out, "    {};", declaration).unwrap();
+    }
+    writeln!(out, "}} {};", name).unwrap();
+    writeln!(
+        out,
+        "_Static_assert(sizeof({name}) == {size}, \"{name} size\");"
+    )
+    .unwrap();
+    for (field, offset) in binding.ctx.fields.iter().zip(offsets) {
+        let field = &field.ident;
+        writeln!(
+            out,
+            "_Static_assert(offsetof({name}, {field}) == {offset}, \"{name}.{field} offset\");"
+        )
+        .unwrap();
+    }
+    out.push('\n');
+}
+
+/// A number which differs on 32 and 64 bit targets, as a C expression
+fn by_width(n32: usize, n64: usize) -> String {
+    match n32 == n64 {
+        true => n32.to_string(),
+        false => format!("(sizeof(void *) == 8 ? {} : {})", n64, n32),
+    }
+}
+
+/// The name of the view of a struct
+fn view(binding: &Binding) -> String {
+    binding.ctx.path.clone().into_view().ident.to_string()
+}
+
+/// `serde_bindgen_core::StrView`
+const STR_VIEW: &str = r#"typedef struct StrView {
+    const char *ptr;
+    size_t len;
+} StrView;
+_Static_assert(sizeof(StrView) == (sizeof(void *) == 8 ? 16 : 8), "StrView size");
+_Static_assert(offsetof(StrView, len) == (sizeof(void *) == 8 ? 8 : 4), "StrView.len offset");"#;
+
+/// The types of the runtime which bindings take as arguments, as the runtime
+/// lays them out
+const RUNTIME: &[(&str, &str)] = &[
+    (
+        "WriteFn",
+        "typedef int32_t (*WriteFn)(void *ctx, const uint8_t *bytes, size_t len);",
+    ),
+    (
+        "ChunkState",
+        r#"typedef struct ChunkState {
+    uint32_t cursor[8];
+    uint32_t skip;
+    bool done;
+} ChunkState;
+_Static_assert(sizeof(ChunkState) == 40, "ChunkState size");
+_Static_assert(offsetof(ChunkState, cursor) == 0, "ChunkState.cursor offset");
+_Static_assert(offsetof(ChunkState, skip) == 32, "ChunkState.skip offset");
+_Static_assert(offsetof(ChunkState, done) == 36, "ChunkState.done offset");"#,
+    ),
+    (
+        "FeedState",
+        r#"typedef struct FeedState {
+    uint16_t opaque[39];
+} FeedState;
+_Static_assert(sizeof(FeedState) == 78, "FeedState size");"#,
+    ),
+];
+
+/// The bindings sorted so that every struct follows the structs it contains
+pub fn order(found: &[Binding]) -> Result<Vec<&Binding>> {
+    fn visit<'a>(
+        binding: &'a Binding,
+        found: &'a [Binding],
+        sorted: &mut Vec<&'a Binding>,
+    ) -> Result<()> {
+        if sorted.iter().any(|b| std::ptr::eq(*b, binding)) {
+            return Ok(());
+        }
+        for field in &binding.ctx.fields {
+            if let FieldType::Struct(p) = field.ty.innermost() {
+                match found.iter().find(|b| b.ctx.path.ident == p.ident) {
+                    Some(nested) => visit(nested, found, sorted)?,
+                    None => {
+                        let msg = format!("{} has no #[binding]", p.ident);
+                        return Err(Error::new(p.ident.span(), msg));
+                    }
+                }
+            }
+        }
+        sorted.push(binding);
+        Ok(())
+    }
+    let mut sorted = Vec::new();
+    for binding in found {
+        visit(binding, found, &mut sorted)?;
+    }
+    Ok(sorted)
+}
+
+/// The C layout of an owned struct
+pub struct Layout {
+    pub size: usize,
+    pub align: usize,
+    pub offsets: Vec<usize>,
+    pub abi_hash: u64,
+}
+
+/// The layout of every binding by the name of its original struct. The
+/// bindings must be in dependency order
+pub fn layouts(sorted: &[&Binding]) -> Result<HashMap<String, Layout>> {
+    let mut layouts = HashMap::new();
+    for binding in sorted {
+        let layout = layout(binding, &layouts)?;
+        layouts.insert(binding.ctx.path.ident.to_string(), layout);
+    }
+    Ok(layouts)
+}
+
+/// The PB_MAX_LEN of each struct by the name of its original struct. Nested
+/// structs come first in sorted
+pub fn pb_max_lens(sorted: &[&Binding]) -> Result<HashMap<String, usize>> {
+    let mut lens: HashMap<String, usize> = HashMap::new();
+    for binding in sorted {
+        let tags = binding.ctx.pb_tags()?;
+        let (len, remotes) = binding.ctx.pb_max_len(&tags);
+        let len = remotes.iter().fold(len, |acc, (remote, key, n)| {
+            let remote = lens[&remote.ident.to_string()];
+            acc + (key + utils::varint_len(remote as u64) + remote) * n
+        });
+        lens.insert(binding.ctx.path.ident.to_string(), len);
+    }
+    Ok(lens)
+}
+
+/// Lay out an owned struct like `serde_bindgen_core::layout::Layout::new`
+/// and hash it like `serde_bindgen_core::layout::AbiHash`. The layouts of
+/// the structs it contains must be known already
+fn layout(binding: &Binding, layouts: &HashMap<String, Layout>) -> Result<Layout> {
+    let nested = |p: &PathNamed| {
+        layouts
+            .get(&p.ident.to_string())
+            .map(|layout| (layout.size, layout.align))
+    };
+    let (size, align, offsets) = lay_out(&binding.ctx, &nested)?;
+    let mut hash = Fnv::new();
+    for field in &binding.ctx.fields {
+        let (abi, remote) = field.abi();
+        hash.bytes(abi.as_bytes());
+        if let Some(remote) = remote {
+            hash.u64(layouts[&remote.ident.to_string()].abi_hash);
+        }
+    }
+    hash.u64(size as u64);
+    hash.u64(align as u64);
+    for offset in &offsets {
+        hash.u64(*offset as u64);
+    }
+    Ok(Layout {
+        size,
+        align,
+        offsets,
+        abi_hash: hash.0,
+    })
+}
+
+/// The layout of the view of every binding of the serde mode, by the name of
+/// the view, for pointers of the width. The bindings must be in
+/// dependency order
+fn view_layouts(sorted: &[&Binding], pointer: usize) -> Result<HashMap<String, Layout>> {
+    let mut layouts: HashMap<String, Layout> = HashMap::new();
+    for binding in sorted {
+        let nested = |p: &PathNamed| match p.ident == "StrView" {
+            true => Some((2 * pointer, pointer)),
+            false => layouts
+                .get(&p.ident.to_string())
+                .map(|layout| (layout.size, layout.align)),
+        };
+        let (size, align, offsets) = lay_out(&binding.ctx.clone().into_view(), &nested)?;
+        let layout = Layout {
+            size,
+            align,
+            offsets,
+            abi_hash: 0,
+        };
+        layouts.insert(view(binding), layout);
+    }
+    Ok(layouts)
+}
+
+/// Lay out the fields of a struct in order as `#[repr(C)]` does, given the
+/// size and alignment of the structs they contain. IE: (size, align, offsets)
+fn lay_out<F>(ctx: &Context, nested: &F) -> Result<(usize, usize, Vec<usize>)>
+where
+    F: Fn(&PathNamed) -> Option<(usize, usize)>,
+{
+    let mut offsets = Vec::new();
+    let mut offset = 0usize;
+    let mut align = 1;
+    for field in &ctx.fields {
+        let (size, field_align) = field
+            .ty
+            .layout(&field.attributes, nested)
+            .ok_or_else(|| Error::new(field.ident.span(), "unsupported type"))?;
+        offset = offset.div_ceil(field_align) * field_align;
+        offsets.push(offset);
+        offset += size;
+        align = align.max(field_align);
+    }
+    Ok((offset.div_ceil(align) * align, align, offsets))
+}
+
+/// 64 bit FNV-1a
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Fnv {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn u64(&mut self, n: u64) {
+        self.bytes(&n.to_le_bytes());
+    }
+}
+
+/// The C declaration of a field of an owned struct, or of a view
+fn declaration(field: &Field, owned: &HashMap<String, String>, view: bool) -> Result<String> {
+    let mut dims: String = field.ty.dims().iter().map(|n| format!("[{}]", n)).collect();
+    let ty = match field.ty.innermost() {
+        FieldType::RefStr(_) if view => "StrView".to_string(),
+        FieldType::RefStr(_) => {
+            write!(dims, "[{}]", field.attributes.seek_len()).unwrap();
+            "char".to_string()
+        }
+        FieldType::Struct(p) if view => p.clone().into_view().ident.to_string(),
+        FieldType::Struct(p) => owned[&p.ident.to_string()].clone(),
+        FieldType::Primative(p) => primitive(&p.to_string())
+            .ok_or_else(|| Error::new(p.span(), "unsupported type"))?
+            .to_string(),
+        FieldType::Array(_) => unreachable!(),
+    };
+    Ok(format!("{} {}{}", ty, field.ident, dims))
+}
+
+fn primitive(ty: &str) -> Option<&'static str> {
+    match ty {
+        "bool" => Some("bool"),
+        "u8" => Some("uint8_t"),
+        "i8" => Some("int8_t"),
+        "u16" => Some("uint16_t"),
+        "i16" => Some("int16_t"),
+        "u32" => Some("uint32_t"),
+        "i32" => Some("int32_t"),
+        "u64" => Some("uint64_t"),
+        "usize" => Some("size_t"),
+        "c_void" => Some("void"),
+        _ => None,
+    }
+}
+
+/// The C prototype of an extern function
+fn prototype(
+    sig: &syn::Signature,
+    types: &HashMap<String, String>,
+    named: &mut BTreeSet<String>,
+) -> Result<String> {
+    let ret = match &sig.output {
+        syn::ReturnType::Default => "void".to_string(),
+        syn::ReturnType::Type(_, ty) => c_type(ty, types, named)?,
+    };
+    let params = sig
+        .inputs
+        .iter()
+        .map(|input| match input {
+            syn::FnArg::Typed(arg) => {
+                let ty = c_type(&arg.ty, types, named)?;
+                let name = c_ident(arg.pat.to_token_stream().to_string());
+                match ty.ends_with('*') {
+                    true => Ok(format!("{}{}", ty, name)),
+                    false => Ok(format!("{} {}", ty, name)),
+                }
+            }
+            syn::FnArg::Receiver(r) => Err(Error::new(r.span(), "unexpected self")),
+        })
+        .collect::<Result<Vec<String>>>()?;
+    let params = match params.is_empty() {
+        true => "void".to_string(),
+        false => params.join(", "),
+    };
+    Ok(format!("{} {}({})", ret, sig.ident, params))
+}
+
+/// A parameter name which is not reserved by C or C++. IE: `new` => `new_`
+fn c_ident(name: String) -> String {
+    const RESERVED: &[&str] = &[
+        "auto",
+        "char",
+        "class",
+        "default",
+        "delete",
+        "double",
+        "float",
+        "int",
+        "long",
+        "new",
+        "operator",
+        "private",
+        "protected",
+        "public",
+        "register",
+        "short",
+        "signed",
+        "template",
+        "this",
+        "throw",
+        "typename",
+        "unsigned",
+        "virtual",
+        "volatile",
+    ];
+    match RESERVED.contains(&name.as_str()) {
+        true => name + "_",
+        false => name,
+    }
+}
+
+/// The C spelling of a rust type from an extern function signature. The
+/// names of the structs it refers to are collected in named
+fn c_type(
+    ty: &syn::Type,
+    types: &HashMap<String, String>,
+    named: &mut BTreeSet<String>,
+) -> Result<String> {
+    let pointer = |elem: &syn::Type, mutable: bool, named: &mut BTreeSet<String>| {
+        let elem = c_type(elem, types, named)?;
+        match mutable {
+            true => Ok(format!("{} *", elem)),
+            false => Ok(format!("const {} *", elem)),
+        }
+    };
+    match ty {
+        syn::Type::Reference(r) => pointer(&r.elem, r.mutability.is_some(), named),
+        syn::Type::Ptr(p) => pointer(&p.elem, p.mutability.is_some(), named),
+        syn::Type::Path(p) => {
+            let ident = &p.path.segments.last().unwrap().ident;
+            let name = ident.to_string();
+            if let Some(primitive) = primitive(&name) {
+                return Ok(primitive.to_string());
+            }
+            let name = types.get(&name).cloned().unwrap_or(name);
+            named.insert(name.clone());
+            Ok(name)
+        }
+        ty => Err(Error::new(ty.span(), "unsupported type")),
+    }
+}
@@ -17,17 +17,26 @@
 // @author Thomas Chiantia <thomas.chiantia@gmail.com>
 // @date 2022
 
-use crate::attributes::DefaultLit;
-use syn::parse_quote;
+//! The model of a `#[binding]` struct, shared by the derive and sbc-gen.
+//!
+//! The derive turns a struct into its rust items with [`context`], and
+//! sbc-gen scans a crate with [`header::scan`] and renders the structs it
+//! finds to the other languages. Neither reads the cargo features of this
+//! crate, the caller hands the enabled ones in as [`attributes::Features`].
 
-#[test]
-fn can_peek_is_array() {
-    let attr: DefaultLit = parse_quote!(["hello", "world"]);
-    assert!(attr.is_array());
-}
+#[cfg(test)]
+mod tests;
 
-#[test]
-fn can_peek_is_not_array() {
-    let attr: DefaultLit = parse_quote!(32);
-    assert!(!attr.is_array());
-}
+pub mod attributes;
+pub mod context;
+pub mod cpp;
+pub mod field;
+pub mod header;
+pub mod keyword;
+pub mod naming;
+pub mod path;
+pub mod proto;
+pub mod python;
+pub mod schema;
+pub mod typescript;
+pub mod utils;
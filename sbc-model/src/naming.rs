@@ -0,0 +1,146 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! The names of generated symbols and types.
+//!
+//! A binding symbol comes from the container "symbol" template, in which
+//! `{prefix}`, `{verb}`, `{type}` and `{variant}` are replaced. `{type}` is
+//! the snake case name of the struct the binding takes, IE: `foo`,
+//! `foo_owned` or `foo_borrowed`. `{variant}` is `_chunk` or `_feed` for the
+//! bindings which have one and empty otherwise. A template without it gets
+//! the variant after the verb.
+//!
+//! The containers "owned" and "borrowed" options add type aliases, so that C
+//! code can use its own names for the generated structs, and name `{type}`
+//! for the bindings which take them.
+
+use heck::AsSnakeCase;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::Result;
+use syn::Ident;
+
+use super::attributes::ContainerAttributes;
+use super::path::PathNamed;
+
+/// Which struct generated from the original a binding takes
+#[derive(Clone, Copy)]
+pub enum Form {
+    Original,
+    Owned,
+    Borrowed,
+    View,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug))]
+pub struct Naming {
+    pub prefix: String,
+    pub symbol: String,
+    pub owned: Option<Ident>,
+    pub borrowed: Option<Ident>,
+}
+
+impl Naming {
+    /// The default symbol template. IE: `sbc_parse_foo`
+    pub const SYMBOL: &'static str = "{prefix}_{verb}_{type}{variant}";
+
+    pub fn new(prefix: &str) -> Naming {
+        Naming {
+            prefix: prefix.to_string(),
+            symbol: Naming::SYMBOL.to_string(),
+            owned: None,
+            borrowed: None,
+        }
+    }
+
+    pub fn from_container(attrs: &ContainerAttributes) -> Result<Naming> {
+        let mut naming = Naming::new("sbc");
+        if let Some(prefix) = attrs.seek_val("prefix") {
+            naming.prefix = prefix.value();
+        }
+        if let Some(symbol) = attrs.seek_val("symbol") {
+            naming.symbol = symbol.value();
+            if !naming.symbol.contains("{verb}") || !naming.symbol.contains("{type}") {
+                let msg = "expected a symbol template with {verb} and {type}";
+                return Err(syn::Error::new(symbol.span(), msg));
+            }
+        }
+        let ident = |lit: &syn::LitStr| lit.parse::<Ident>();
+        naming.owned = attrs.seek_val("owned").map(ident).transpose()?;
+        naming.borrowed = attrs.seek_val("borrowed").map(ident).transpose()?;
+        Ok(naming)
+    }
+
+    /// The symbol of the binding which does verb with the form of path
+    pub fn symbol(&self, verb: &str, form: Form, path: &PathNamed) -> Ident {
+        self.variant_symbol(verb, "", form, path)
+    }
+
+    /// The symbol of the variant of a binding, IE: `sbc_print_foo_owned_chunk`
+    pub fn variant_symbol(&self, verb: &str, variant: &str, form: Form, path: &PathNamed) -> Ident {
+        let variant = match variant {
+            "" => String::new(),
+            variant => format!("_{}", variant),
+        };
+        let (verb, variant) = match self.symbol.contains("{variant}") {
+            true => (verb.to_string(), variant),
+            false => (format!("{}{}", verb, variant), String::new()),
+        };
+        let alias = match form {
+            Form::Owned => self.owned.as_ref(),
+            Form::Borrowed => self.borrowed.as_ref(),
+            Form::Original | Form::View => None,
+        };
+        let ty = match (alias, form) {
+            (Some(alias), _) => alias.to_string(),
+            (None, Form::Original) => path.ident.to_string(),
+            (None, Form::Owned) => path.clone().into_owned().ident.to_string(),
+            (None, Form::Borrowed) => path.clone().into_borrowed().ident.to_string(),
+            (None, Form::View) => path.clone().into_view().ident.to_string(),
+        };
+        let symbol = self
+            .symbol
+            .replace("{prefix}", &self.prefix)
+            .replace("{verb}", &verb)
+            .replace("{type}", &AsSnakeCase(ty).to_string())
+            .replace("{variant}", &variant);
+        Ident::new(&symbol, path.ident.span())
+    }
+
+    /// The type aliases requested for the owned and borrowed structs
+    pub fn aliases(&self, path: &PathNamed) -> TokenStream {
+        let (_original, borrowed, owned) = path.split_self_for_impl();
+        let owned = self.owned.as_ref().map(|name| {
+            quote! {
+                #[allow(non_camel_case_types)]
+                pub type #name = #owned;
+            }
+        });
+        let borrowed = self.borrowed.as_ref().map(|name| {
+            let mut alias = borrowed.clone();
+            alias.rename(&name.to_string());
+            quote! {
+                #[allow(non_camel_case_types)]
+                pub type #alias = #borrowed;
+            }
+        });
+        quote! {#owned #borrowed}
+    }
+}
@@ -75,6 +75,48 @@ impl PathNamed {
         self
     }
 
+    pub fn into_shouty_cbor_max_len(mut self) -> Self {
+        let max_len = AsShoutySnakeCase(format!("{}_CBOR_MAX_LEN", self.ident)).to_string();
+        self.rename(&max_len).strip_generics();
+        self
+    }
+
+    pub fn into_shouty_msgpack_max_len(mut self) -> Self {
+        let max_len = AsShoutySnakeCase(format!("{}_MSGPACK_MAX_LEN", self.ident)).to_string();
+        self.rename(&max_len).strip_generics();
+        self
+    }
+
+    pub fn into_shouty_descriptor(mut self) -> Self {
+        let descriptor = AsShoutySnakeCase(format!("{}_DESCRIPTOR", self.ident)).to_string();
+        self.rename(&descriptor).strip_generics();
+        self
+    }
+
+    pub fn into_shouty_layout(mut self) -> Self {
+        let layout = AsShoutySnakeCase(format!("{}_LAYOUT", self.ident)).to_string();
+        self.rename(&layout).strip_generics();
+        self
+    }
+
+    pub fn into_shouty_abi_hash(mut self) -> Self {
+        let hash = AsShoutySnakeCase(format!("{}_ABI_HASH", self.ident)).to_string();
+        self.rename(&hash).strip_generics();
+        self
+    }
+
+    pub fn into_shouty_pb_max_len(mut self) -> Self {
+        let max_len = AsShoutySnakeCase(format!("{}_PB_MAX_LEN", self.ident)).to_string();
+        self.rename(&max_len).strip_generics();
+        self
+    }
+
+    pub fn into_shouty_fields(mut self) -> Self {
+        let fields = AsShoutySnakeCase(format!("{}_FIELDS", self.ident)).to_string();
+        self.rename(&fields).strip_generics();
+        self
+    }
+
     pub fn as_borrowed(&mut self) -> &mut Self {
         self.rename(&format!("{}Borrowed", self.ident));
         self
@@ -85,6 +127,25 @@ impl PathNamed {
         self
     }
 
+    pub fn as_view(&mut self) -> &mut Self {
+        self.rename(&format!("{}View", self.ident)).strip_generics();
+        self
+    }
+
+    pub fn into_view(mut self) -> Self {
+        self.as_view();
+        self
+    }
+
+    pub fn as_partial(&mut self) -> &mut Self {
+        self.rename(&format!("{}Partial", self.ident))
+    }
+
+    pub fn into_partial(mut self) -> Self {
+        self.as_partial();
+        self
+    }
+
     pub fn rename(&mut self, name: &str) -> &mut Self {
         self.ident = Ident::new(name, self.ident.span());
         self
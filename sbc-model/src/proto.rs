@@ -0,0 +1,119 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Render a Protocol Buffers schema (proto3) for the `#[binding]` structs of
+//! a crate. Used by the sbc-gen binary.
+//!
+//! Every struct is a message named like the rust struct, with the field
+//! numbers the pb_encode and pb_decode bindings use. Arrays are repeated
+//! fields, flattened in row major order. The lengths of strings and arrays
+//! are given as nanopb options, so nanopb generates C structs with the same
+//! fields as the owned structs. Other generators ignore them, but need
+//! `nanopb.proto` on their include path.
+
+use std::fmt::Write;
+
+use syn::parse::{Error, Result};
+
+use crate::field::{Field, FieldType};
+use crate::header::{docs, Binding};
+
+/// Render one .proto file with a message for every binding
+pub fn render(found: &[Binding]) -> Result<String> {
+    let mut out = String::new();
+    writeln!(out, "// Generated by sbc-gen, do not edit").unwrap();
+    writeln!(out, "syntax = \"proto3\";\n").unwrap();
+    writeln!(out, "import \"nanopb.proto\";").unwrap();
+    for (n, binding) in found.iter().enumerate() {
+        let ident = &binding.ctx.path.ident;
+        if found[..n].iter().any(|b| b.ctx.path.ident == *ident) {
+            let msg = format!("{} is declared more than once", ident);
+            return Err(Error::new(ident.span(), msg));
+        }
+        out.push('\n');
+        comment(&mut out, &binding.docs, "");
+        writeln!(out, "message {} {{", ident).unwrap();
+        let tags = binding.ctx.pb_tags()?;
+        for (field, tag) in binding.ctx.fields.iter().zip(tags) {
+            comment(&mut out, &docs(&field.attributes), "  ");
+            writeln!(out, "  {};", declaration(field, tag)?).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+    Ok(out)
+}
+
+fn comment(out: &mut String, docs: &[String], indent: &str) {
+    for line in docs {
+        match line.is_empty() {
+            true => writeln!(out, "{}//", indent).unwrap(),
+            false => writeln!(out, "{}// {}", indent, line).unwrap(),
+        }
+    }
+}
+
+/// A field with its nanopb options. IE: `repeated string tags = 5 [...]`
+fn declaration(field: &Field, tag: u32) -> Result<String> {
+    let unsupported = || Error::new(field.ident.span(), "unsupported type");
+    let mut options = Vec::new();
+    let ty = match field.ty.innermost() {
+        FieldType::Primative(p) if p == "bool" => "bool".to_string(),
+        FieldType::Primative(p) => {
+            let (ty, size) = scalar(&p.to_string()).ok_or_else(unsupported)?;
+            if let Some(size) = size {
+                options.push(format!("(nanopb).int_size = {}", size));
+            }
+            ty.to_string()
+        }
+        // nanopb counts the NUL terminator, as the owned string does
+        FieldType::RefStr(_) => {
+            let len = field.attributes.seek_len();
+            options.push(format!("(nanopb).max_size = {}", len));
+            "string".to_string()
+        }
+        FieldType::Struct(p) => p.ident.to_string(),
+        FieldType::Array(_) => return Err(unsupported()),
+    };
+    let label = match field.ty.dims().is_empty() {
+        true => "",
+        false => {
+            options.push(format!("(nanopb).max_count = {}", field.ty.count()));
+            options.push("(nanopb).fixed_count = true".to_string());
+            "repeated "
+        }
+    };
+    let mut declaration = format!("{}{} {} = {}", label, ty, field.ident, tag);
+    if !options.is_empty() {
+        write!(declaration, " [{}]", options.join(", ")).unwrap();
+    }
+    Ok(declaration)
+}
+
+/// The protobuf type of an integer, and the nanopb size when it is smaller
+fn scalar(ty: &str) -> Option<(&'static str, Option<&'static str>)> {
+    match ty {
+        "u8" => Some(("uint32", Some("IS_8"))),
+        "i8" => Some(("sint32", Some("IS_8"))),
+        "u16" => Some(("uint32", Some("IS_16"))),
+        "i16" => Some(("sint32", Some("IS_16"))),
+        "u32" => Some(("uint32", None)),
+        "i32" => Some(("sint32", None)),
+        _ => None,
+    }
+}
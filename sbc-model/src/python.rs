@@ -0,0 +1,263 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Render a python module which drives the C ABI of the `#[binding]` structs
+//! of a crate through ctypes. Used by the sbc-gen binary.
+//!
+//! The module declares a `ctypes.Structure` for every owned struct (with the
+//! layout checked against the one the header asserts), the MAX_LEN and
+//! ABI_HASH constants, and a `load` function which opens the library and
+//! declares the prototype of every extern function. `to_dict` and
+//! `from_dict` convert an owned struct to and from the JSON shape.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use syn::parse::{Error, Result};
+use syn::spanned::Spanned;
+
+use crate::attributes::Features;
+use crate::field::FieldType;
+use crate::header::{layouts, order, Binding};
+use crate::naming::Form;
+
+/// The helpers shared by every generated module
+const HELPERS: &str = r#"
+WriteFn = ctypes.CFUNCTYPE(ctypes.c_int32, ctypes.c_void_p, ctypes.POINTER(ctypes.c_uint8), ctypes.c_size_t)
+
+
+def _is_str(ty):
+    return issubclass(ty, ctypes.Array) and ty._type_ is ctypes.c_char
+
+
+def _load(ty, value):
+    if issubclass(ty, ctypes.Structure):
+        fields = dict(ty._fields_)
+        return {key: _load(fields[name], getattr(value, name)) for name, key in ty._keys_}
+    if _is_str(ty):
+        return bytes(value).split(b"\0")[0].decode()
+    if issubclass(ty, ctypes.Array):
+        return [_load(ty._type_, item) for item in value]
+    return value
+
+
+def _store(ty, get, put, data):
+    if issubclass(ty, ctypes.Structure):
+        target = get()
+        fields = dict(ty._fields_)
+        for name, key in ty._keys_:
+            _store(
+                fields[name],
+                lambda: getattr(target, name),
+                lambda v: setattr(target, name, v),
+                data[key],
+            )
+    elif _is_str(ty):
+        encoded = data.encode()
+        if len(encoded) >= ty._length_:
+            raise ValueError("%r is longer than %d bytes" % (data, ty._length_ - 1))
+        put(encoded)
+    elif issubclass(ty, ctypes.Array):
+        if len(data) != ty._length_:
+            raise ValueError("expected %d items, found %d" % (ty._length_, len(data)))
+        target = get()
+        for i, item in enumerate(data):
+            # A string in an array is written through its value, not replaced
+            if _is_str(ty._type_):
+                put_item = lambda v: setattr(target[i], "value", v)
+            else:
+                put_item = lambda v: target.__setitem__(i, v)
+            _store(ty._type_, lambda: target[i], put_item, item)
+    else:
+        put(data)
+
+
+def to_dict(value):
+    """The JSON shape of an owned struct"""
+    return _load(type(value), value)
+
+
+def from_dict(cls, data):
+    """A new owned struct of type cls, filled from its JSON shape"""
+    value = cls()
+    _store(cls, lambda: value, None, data)
+    return value
+"#;
+
+/// Render the module for the bindings of the features
+pub fn render(found: &[Binding], features: &Features) -> Result<String> {
+    let sorted = order(found)?;
+    let layouts = layouts(&sorted)?;
+
+    // The python names of the types prototypes may point at
+    let mut types: HashMap<String, String> = HashMap::new();
+    for binding in found {
+        let (_original, _borrowed, owned) = binding.ctx.path.split_self_for_impl();
+        types.insert(owned.ident.to_string(), binding.owned());
+    }
+
+    let mut out = String::new();
+    writeln!(out, "# Generated by sbc-gen, do not edit\n\nimport ctypes").unwrap();
+    out.push_str(HELPERS);
+    out.push('\n');
+    for binding in &sorted {
+        let (len, remotes) = binding.ctx.weight();
+        let name = binding.ctx.path.clone().into_shouty_max_len().ident;
+        let remotes: String = remotes
+            .iter()
+            .map(|(remote, n)| {
+                let remote = (*remote).clone().into_shouty_max_len().ident;
+                format!(" + {} * {}", remote, n)
+            })
+            .collect();
+        writeln!(out, "{} = {}{}", name, len, remotes).unwrap();
+        let name = binding.ctx.path.clone().into_shouty_abi_hash().ident;
+        let hash = layouts[&binding.ctx.path.ident.to_string()].abi_hash;
+        writeln!(out, "{} = 0x{:016x}", name, hash).unwrap();
+    }
+
+    for binding in &sorted {
+        let name = binding.owned();
+        write!(out, "\n\nclass {}(ctypes.Structure):\n", name).unwrap();
+        if !binding.docs.is_empty() {
+            writeln!(out, "    \"\"\"{}\"\"\"\n", binding.docs.join("\n    ")).unwrap();
+        }
+        writeln!(out, "    _fields_ = [").unwrap();
+        for field in &binding.ctx.fields {
+            let mut ty = match field.ty.innermost() {
+                FieldType::Primative(p) => primitive(&p.to_string())
+                    .ok_or_else(|| Error::new(p.span(), "unsupported type"))?
+                    .to_string(),
+                FieldType::RefStr(_) => {
+                    format!("ctypes.c_char * {}", field.attributes.seek_len())
+                }
+                FieldType::Struct(p) => types[&p.clone().into_owned().ident.to_string()].clone(),
+                FieldType::Array(_) => unreachable!(),
+            };
+            for n in field.ty.dims().into_iter().rev() {
+                ty = match ty.contains(' ') {
+                    true => format!("({}) * {}", ty, n),
+                    false => format!("{} * {}", ty, n),
+                };
+            }
+            writeln!(out, "        ({:?}, {}),", field.ident.to_string(), ty).unwrap();
+        }
+        writeln!(out, "    ]").unwrap();
+        writeln!(out, "    _keys_ = [").unwrap();
+        for field in &binding.ctx.fields {
+            let key = field.wire_name(binding.ctx.rename_all);
+            writeln!(out, "        ({:?}, {:?}),", field.ident.to_string(), key).unwrap();
+        }
+        writeln!(out, "    ]").unwrap();
+        let layout = &layouts[&binding.ctx.path.ident.to_string()];
+        writeln!(out, "\n\nassert ctypes.sizeof({}) == {}", name, layout.size).unwrap();
+        for (field, offset) in binding.ctx.fields.iter().zip(&layout.offsets) {
+            writeln!(out, "assert {}.{}.offset == {}", name, field.ident, offset).unwrap();
+        }
+    }
+
+    write!(
+        out,
+        "\n\ndef load(path):\n    \"\"\"Open the library and declare its bindings\"\"\"\n    lib = ctypes.CDLL(path)\n"
+    )
+    .unwrap();
+    for binding in found {
+        let table = binding.attrs.table()?;
+        let enabled = binding.attrs.bindings(features)?;
+        let file: syn::File = syn::parse2(binding.ctx.bindings(&binding.naming, &enabled, table))?;
+        for item in file.items {
+            if let syn::Item::Fn(f) = item {
+                let name = &f.sig.ident;
+                let args = f
+                    .sig
+                    .inputs
+                    .iter()
+                    .map(|input| match input {
+                        syn::FnArg::Typed(arg) => ctype(&arg.ty, &types),
+                        syn::FnArg::Receiver(r) => Err(Error::new(r.span(), "unexpected self")),
+                    })
+                    .collect::<Result<Vec<String>>>()?;
+                let ret = match &f.sig.output {
+                    syn::ReturnType::Default => "None".to_string(),
+                    syn::ReturnType::Type(_, ty) => ctype(ty, &types)?,
+                };
+                writeln!(out, "    lib.{}.argtypes = [{}]", name, args.join(", ")).unwrap();
+                writeln!(out, "    lib.{}.restype = {}", name, ret).unwrap();
+            }
+        }
+        // Refuse a library built from different structs
+        if enabled.has("abi_hash") {
+            let symbol = binding
+                .naming
+                .symbol("abi_hash", Form::Original, &binding.ctx.path);
+            let hash = binding.ctx.path.clone().into_shouty_abi_hash().ident;
+            writeln!(out, "    if lib.{}() != {}:", symbol, hash).unwrap();
+            writeln!(
+                out,
+                "        raise ImportError(\"%s was built from a different {}\" % path)",
+                binding.ctx.path.ident
+            )
+            .unwrap();
+        }
+    }
+    writeln!(out, "    return lib").unwrap();
+    Ok(out)
+}
+
+fn primitive(ty: &str) -> Option<&'static str> {
+    match ty {
+        "bool" => Some("ctypes.c_bool"),
+        "u8" => Some("ctypes.c_uint8"),
+        "i8" => Some("ctypes.c_int8"),
+        "u16" => Some("ctypes.c_uint16"),
+        "i16" => Some("ctypes.c_int16"),
+        "u32" => Some("ctypes.c_uint32"),
+        "i32" => Some("ctypes.c_int32"),
+        "u64" => Some("ctypes.c_uint64"),
+        "usize" => Some("ctypes.c_size_t"),
+        _ => None,
+    }
+}
+
+/// The ctypes spelling of a rust type from an extern function signature.
+/// Pointers to anything but an owned struct or a number are `c_void_p`
+fn ctype(ty: &syn::Type, types: &HashMap<String, String>) -> Result<String> {
+    let elem = match ty {
+        syn::Type::Reference(r) => &r.elem,
+        syn::Type::Ptr(p) => &p.elem,
+        syn::Type::Path(p) => {
+            let name = p.path.segments.last().unwrap().ident.to_string();
+            return match (primitive(&name), name.as_str()) {
+                (Some(primitive), _) => Ok(primitive.to_string()),
+                (None, "WriteFn") => Ok(name),
+                _ => Err(Error::new(ty.span(), "unsupported type")),
+            };
+        }
+        ty => return Err(Error::new(ty.span(), "unsupported type")),
+    };
+    let name = match &**elem {
+        syn::Type::Path(p) => p.path.segments.last().unwrap().ident.to_string(),
+        _ => return Err(Error::new(elem.span(), "unsupported type")),
+    };
+    match (primitive(&name), types.get(&name)) {
+        (Some(primitive), _) => Ok(format!("ctypes.POINTER({})", primitive)),
+        (None, Some(owned)) => Ok(format!("ctypes.POINTER({})", owned)),
+        (None, None) => Ok("ctypes.c_void_p".to_string()),
+    }
+}
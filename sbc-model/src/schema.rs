@@ -0,0 +1,264 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Render a JSON Schema (draft 2020-12) for the `#[binding]` structs of a
+//! crate. Used by the sbc-gen binary.
+//!
+//! Every struct is a definition under `$defs`, named like the rust struct,
+//! and nested structs refer to each other with `$ref`. The schema accepts
+//! what the generated parse bindings accept: every field is required, keys
+//! are renamed by `rename_all`, and unknown keys are allowed.
+//!
+//! One difference remains: a string's `maxLength` counts code points, while
+//! the owned buffer limits UTF-8 bytes. The limit is exact for ASCII, but a
+//! string of non-ASCII characters can pass the schema and still be cut short.
+
+use std::fmt::Write;
+
+use syn::parse::{Error, Result};
+
+use crate::attributes::DefaultLit;
+use crate::field::{Field, FieldType};
+use crate::header::{docs, Binding};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The subset of JSON a schema needs
+#[derive(Clone)]
+#[cfg_attr(feature = "testing", derive(Debug, PartialEq))]
+pub enum Json {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn object(members: Vec<(&str, Json)>) -> Json {
+        Json::Object(
+            members
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    /// The value on one line
+    pub fn compact(&self) -> String {
+        let mut out = String::new();
+        match self {
+            Json::Array(items) => {
+                let items = items.iter().map(Json::compact).collect::<Vec<_>>();
+                write!(out, "[{}]", items.join(", ")).unwrap();
+            }
+            Json::Object(members) => {
+                let members = members.iter().map(|(key, value)| {
+                    let mut member = String::new();
+                    write_str(&mut member, key);
+                    format!("{}: {}", member, value.compact())
+                });
+                write!(out, "{{{}}}", members.collect::<Vec<_>>().join(", ")).unwrap();
+            }
+            json => json.write(&mut out, 0),
+        }
+        out
+    }
+
+    /// Write the value, indented by two spaces per level
+    pub fn write(&self, out: &mut String, depth: usize) {
+        let indent = |out: &mut String, depth: usize| out.push_str(&"  ".repeat(depth));
+        match self {
+            Json::Bool(b) => write!(out, "{}", b).unwrap(),
+            Json::Int(n) => write!(out, "{}", n).unwrap(),
+            Json::Str(s) => write_str(out, s),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(items) => {
+                out.push_str("[\n");
+                for (i, item) in items.iter().enumerate() {
+                    indent(out, depth + 1);
+                    item.write(out, depth + 1);
+                    out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                indent(out, depth);
+                out.push(']');
+            }
+            Json::Object(members) if members.is_empty() => out.push_str("{}"),
+            Json::Object(members) => {
+                out.push_str("{\n");
+                for (i, (key, value)) in members.iter().enumerate() {
+                    indent(out, depth + 1);
+                    write_str(out, key);
+                    out.push_str(": ");
+                    value.write(out, depth + 1);
+                    out.push_str(if i + 1 < members.len() { ",\n" } else { "\n" });
+                }
+                indent(out, depth);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Render one schema document with a definition for every binding
+pub fn render(found: &[Binding]) -> Result<String> {
+    let defs = found
+        .iter()
+        .map(|binding| Ok((binding.ctx.path.ident.to_string(), schema(binding)?)))
+        .collect::<Result<Vec<(String, Json)>>>()?;
+    let document = Json::object(vec![
+        ("$schema", Json::Str(DRAFT.to_string())),
+        ("$defs", Json::Object(defs)),
+    ]);
+    let mut out = String::new();
+    document.write(&mut out, 0);
+    out.push('\n');
+    Ok(out)
+}
+
+/// The schema of a struct
+pub fn schema(binding: &Binding) -> Result<Json> {
+    let rename_all = binding.ctx.rename_all;
+    let mut properties = Vec::new();
+    let mut required = Vec::new();
+    for field in &binding.ctx.fields {
+        let name = field.wire_name(rename_all);
+        properties.push((name.clone(), property(field)?));
+        required.push(Json::Str(name));
+    }
+    let mut members = vec![("type", Json::Str("object".to_string()))];
+    if !binding.docs.is_empty() {
+        members.push(("description", Json::Str(binding.docs.join("\n"))));
+    }
+    members.push(("properties", Json::Object(properties)));
+    members.push(("required", Json::Array(required)));
+    Ok(Json::object(members))
+}
+
+/// The schema of a field, with its doc comment and default
+fn property(field: &Field) -> Result<Json> {
+    let unsupported = || Error::new(field.ident.span(), "unsupported type");
+    let mut members = match field.ty.innermost() {
+        FieldType::Primative(p) if p == "bool" => vec![("type", Json::Str("boolean".into()))],
+        FieldType::Primative(p) => {
+            let (min, max) = range(&p.to_string()).ok_or_else(unsupported)?;
+            vec![
+                ("type", Json::Str("integer".into())),
+                ("minimum", Json::Int(min)),
+                ("maximum", Json::Int(max)),
+            ]
+        }
+        // The owned string keeps a byte for the NUL terminator. maxLength
+        // counts code points, so only ASCII is held to the byte limit
+        FieldType::RefStr(_) => {
+            let len = field.attributes.seek_len().saturating_sub(1);
+            vec![
+                ("type", Json::Str("string".into())),
+                ("maxLength", Json::Int(len as i64)),
+            ]
+        }
+        FieldType::Struct(p) => {
+            let reference = format!("#/$defs/{}", p.ident);
+            vec![("$ref", Json::Str(reference))]
+        }
+        FieldType::Array(_) => return Err(unsupported()),
+    };
+    for n in field.ty.dims().into_iter().rev() {
+        members = vec![
+            ("type", Json::Str("array".into())),
+            ("items", Json::object(members)),
+            ("minItems", Json::Int(n as i64)),
+            ("maxItems", Json::Int(n as i64)),
+        ];
+    }
+    let docs = docs(&field.attributes);
+    if !docs.is_empty() {
+        members.insert(0, ("description", Json::Str(docs.join("\n"))));
+    }
+    if let Some(default) = field.attributes.seek_default() {
+        members.push(("default", default_value(default, &field.ty.dims())?));
+    }
+    Ok(Json::object(members))
+}
+
+/// The range of an integer type
+fn range(ty: &str) -> Option<(i64, i64)> {
+    match ty {
+        "u8" => Some((0, u8::MAX as i64)),
+        "i8" => Some((i8::MIN as i64, i8::MAX as i64)),
+        "u16" => Some((0, u16::MAX as i64)),
+        "i16" => Some((i16::MIN as i64, i16::MAX as i64)),
+        "u32" => Some((0, u32::MAX as i64)),
+        "i32" => Some((i32::MIN as i64, i32::MAX as i64)),
+        _ => None,
+    }
+}
+
+/// The JSON value of an `sbc: default`. A single value given for an array is
+/// the default of every element, as the init binding treats it
+pub fn default_value(default: &DefaultLit, dims: &[usize]) -> Result<Json> {
+    let expr: syn::Expr = default.parse()?;
+    literal(&expr, dims)
+}
+
+fn literal(expr: &syn::Expr, dims: &[usize]) -> Result<Json> {
+    let unsupported = || Error::new_spanned(expr, "unsupported default");
+    match expr {
+        syn::Expr::Array(array) => {
+            let inner = dims.get(1..).unwrap_or(&[]);
+            let items = array.elems.iter().map(|elem| literal(elem, inner));
+            Ok(Json::Array(items.collect::<Result<Vec<Json>>>()?))
+        }
+        _ if !dims.is_empty() => {
+            let item = literal(expr, &dims[1..])?;
+            Ok(Json::Array(vec![item; dims[0]]))
+        }
+        syn::Expr::Lit(lit) => match &lit.lit {
+            syn::Lit::Bool(b) => Ok(Json::Bool(b.value)),
+            syn::Lit::Str(s) => Ok(Json::Str(s.value())),
+            syn::Lit::Int(n) => Ok(Json::Int(n.base10_parse()?)),
+            _ => Err(unsupported()),
+        },
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match literal(expr, dims)? {
+            Json::Int(n) => Ok(Json::Int(-n)),
+            _ => Err(unsupported()),
+        },
+        _ => Err(unsupported()),
+    }
+}
@@ -0,0 +1,171 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use crate::attributes::{ContainerAttributes, DefaultLit, Features, RenameRule};
+use syn::parse_quote;
+
+#[test]
+fn can_peek_is_array() {
+    let attr: DefaultLit = parse_quote!(["hello", "world"]);
+    assert!(attr.is_array());
+}
+
+#[test]
+fn can_peek_is_not_array() {
+    let attr: DefaultLit = parse_quote!(32);
+    assert!(!attr.is_array());
+}
+
+#[test]
+fn can_rename_field() {
+    let rename = |rule: &str| {
+        RenameRule::parse(rule)
+            .unwrap()
+            .apply_to_field("outcome_of_it")
+    };
+    assert_eq!(rename("lowercase"), "outcome_of_it");
+    assert_eq!(rename("UPPERCASE"), "OUTCOME_OF_IT");
+    assert_eq!(rename("PascalCase"), "OutcomeOfIt");
+    assert_eq!(rename("camelCase"), "outcomeOfIt");
+    assert_eq!(rename("snake_case"), "outcome_of_it");
+    assert_eq!(rename("SCREAMING_SNAKE_CASE"), "OUTCOME_OF_IT");
+    assert_eq!(rename("kebab-case"), "outcome-of-it");
+    assert_eq!(rename("SCREAMING-KEBAB-CASE"), "OUTCOME-OF-IT");
+    assert!(RenameRule::parse("garbage").is_none());
+
+    // nothing left to lower, or a first character which is not ascii
+    let camel = RenameRule::parse("camelCase").unwrap();
+    assert_eq!(camel.apply_to_field("_"), "");
+    assert_eq!(camel.apply_to_field("élan_vital"), "élanVital");
+}
+
+#[test]
+fn can_seek_container_list() {
+    let attrs: ContainerAttributes = parse_quote!(prefix = "foo", skip = ["print", "copy"]);
+    assert_eq!(attrs.seek_val("prefix").unwrap().value(), "foo");
+    assert!(attrs.seek_val("skip").is_none());
+    let skip: Vec<String> = attrs
+        .seek_list("skip")
+        .unwrap()
+        .iter()
+        .map(|lit| lit.value())
+        .collect();
+    assert_eq!(skip, vec!["print", "copy"]);
+    assert!(attrs.seek_list("prefix").is_none());
+}
+
+#[test]
+fn can_select_bindings() {
+    let attrs: ContainerAttributes = parse_quote!(only = ["parse", "init"], skip = ["init"]);
+    assert_eq!(
+        attrs.bindings(&Features::default()).unwrap().0,
+        vec!["parse"]
+    );
+
+    let attrs: ContainerAttributes = parse_quote!(skip = ["print_owned", "copy"]);
+    let bindings = attrs.bindings(&Features::default()).unwrap();
+    assert!(bindings.has("print"));
+    assert!(!bindings.has("print_owned"));
+    assert!(!bindings.has("copy"));
+
+    let attrs: ContainerAttributes = parse_quote!(skip = ["garbage"]);
+    assert!(attrs.bindings(&Features::default()).is_err());
+}
+
+#[test]
+fn can_select_bindings_of_mode() {
+    let attrs: ContainerAttributes = parse_quote!(only = ["parse_owned"]);
+    let err = attrs.bindings(&Features::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no parse_owned binding in mode = \"serde\""
+    );
+
+    let attrs: ContainerAttributes = parse_quote!(mode = "table", skip = ["copy"]);
+    assert!(attrs.bindings(&Features::default()).is_err());
+
+    let attrs: ContainerAttributes =
+        parse_quote!(mode = "table", only = ["descriptor", "parse_owned"]);
+    assert_eq!(
+        attrs.bindings(&Features::default()).unwrap().0,
+        vec!["descriptor", "parse_owned"]
+    );
+    let attrs: ContainerAttributes = parse_quote!(mode = "table");
+    assert!(!attrs.bindings(&Features::default()).unwrap().has("copy"));
+}
+
+#[test]
+fn can_select_descriptor() {
+    let attrs: ContainerAttributes = parse_quote!(prefix = "foo");
+    assert!(attrs
+        .bindings(&Features::default())
+        .unwrap()
+        .has("descriptor"));
+    let attrs: ContainerAttributes = parse_quote!(skip = ["descriptor"]);
+    assert!(!attrs
+        .bindings(&Features::default())
+        .unwrap()
+        .has("descriptor"));
+
+    let attrs: ContainerAttributes = parse_quote!(mode = "table", skip = ["descriptor"]);
+    let err = attrs.bindings(&Features::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "parse_owned requires the descriptor binding"
+    );
+    let attrs: ContainerAttributes = parse_quote!(mode = "table", only = ["print_owned"]);
+    assert!(attrs.bindings(&Features::default()).is_err());
+    let attrs: ContainerAttributes = parse_quote!(
+        mode = "table",
+        skip = ["descriptor", "parse_owned", "print_owned"]
+    );
+    assert!(!attrs
+        .bindings(&Features::default())
+        .unwrap()
+        .has("descriptor"));
+}
+
+#[test]
+fn can_select_bindings_of_features() {
+    let attrs: ContainerAttributes = parse_quote!(prefix = "foo");
+    let bindings = attrs.bindings(&Features(vec!["print"])).unwrap();
+    assert!(bindings.has("print_chunk"));
+    assert!(!bindings.has("parse"));
+    assert!(!bindings.has("cbor_print"));
+
+    let mut features = Features::default();
+    features.extend("cbor, protobuf,cbor").unwrap();
+    assert_eq!(
+        features.0,
+        vec!["init", "copy", "parse", "print", "cbor", "protobuf"]
+    );
+    let bindings = attrs.bindings(&features).unwrap();
+    assert!(bindings.has("cbor_print"));
+    assert!(bindings.has("pb_encode"));
+    assert!(!bindings.has("msgpack_print"));
+    assert!(features.extend("serde").is_err());
+
+    let attrs: ContainerAttributes = parse_quote!(only = ["parse", "pb_encode"]);
+    let err = attrs.bindings(&Features::default()).unwrap_err();
+    assert_eq!(err.to_string(), "pb_encode requires the protobuf feature");
+    let attrs: ContainerAttributes = parse_quote!(only = ["msgpack_parse"]);
+    assert!(attrs.bindings(&Features::default()).is_err());
+    let bindings = attrs.bindings(&Features(vec!["msgpack"])).unwrap();
+    assert_eq!(bindings.0, vec!["msgpack_parse"]);
+}
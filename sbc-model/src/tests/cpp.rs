@@ -0,0 +1,123 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use crate::attributes::Features;
+use crate::cpp::render;
+use crate::header::{scan, Binding};
+use syn::parse_quote;
+
+fn bindings(file: syn::File) -> Vec<Binding> {
+    let mut found = Vec::new();
+    scan(&file.items, &mut found).unwrap();
+    found
+}
+
+#[test]
+fn can_render_cpp() {
+    let found = bindings(parse_quote! {
+        /// A thing
+        #[binding(prefix = "acme")]
+        pub struct Foo<'a> {
+            /// sbc: len = 8
+            host_name: &'a str,
+            /// sbc: len = 4
+            tags: [[&'a str; 2]; 3],
+            level: i8,
+        }
+    });
+    let header = render(&found, "FOO_HPP", "foo.h", &Features::default()).unwrap();
+    let expect = r#"namespace acme {
+
+/// A thing
+class Foo {
+  public:
+    Foo() { acme_init_foo(&raw_); }
+    explicit Foo(const FooOwned &raw) : raw_(raw) {}
+
+    void merge(std::string_view json) {
+        int32_t code = acme_merge_foo(&raw_, reinterpret_cast<const uint8_t *>(json.data()), json.size());
+        if (code < 0) {
+            throw sbc::error("Foo: merge failed", code);
+        }
+    }
+
+    std::string print() const {
+        std::string json(FOO_MAX_LEN, '\0');
+        size_t len = json.size();
+        int32_t code = acme_print_foo_owned(&raw_, reinterpret_cast<uint8_t *>(&json[0]), &len);
+        if (code < 0) {
+            throw sbc::error("Foo: print failed", code);
+        }
+        json.resize(len);
+        return json;
+    }
+
+    std::string_view host_name() const {
+        return sbc::detail::view(raw_.host_name, sizeof(raw_.host_name));
+    }
+
+    std::string_view tags(size_t i0, size_t i1) const {
+        return sbc::detail::view(raw_.tags[i0][i1], sizeof(raw_.tags[i0][i1]));
+    }
+
+    FooOwned &raw() noexcept { return raw_; }
+    const FooOwned &raw() const noexcept { return raw_; }
+
+  private:
+    FooOwned raw_;
+};
+
+} // namespace acme
+"#;
+    assert!(header.starts_with("/* Generated by sbc-gen, do not edit */\n#ifndef FOO_HPP\n"));
+    assert!(header.contains("#include \"foo.h\"\n"));
+    assert!(header.contains(expect));
+}
+
+#[test]
+fn can_render_cpp_table() {
+    let found = bindings(parse_quote! {
+        #[binding(prefix = "acme", mode = "table", owned = "acme_foo_t", skip = ["init", "print_owned"])]
+        pub struct Foo {
+            level: i8,
+        }
+    });
+    let header = render(&found, "FOO_HPP", "foo.h", &Features::default()).unwrap();
+    assert!(header.contains("    Foo() : raw_{} {}\n"));
+    assert!(header.contains("    explicit Foo(const acme_foo_t &raw) : raw_(raw) {}\n"));
+    assert!(header.contains("        int32_t code = acme_parse_acme_foo_t(&raw_,"));
+    assert!(!header.contains("acme_init_foo"));
+    assert!(!header.contains("print()"));
+}
+
+#[test]
+fn can_not_render_cpp_method_clash() {
+    for field in ["raw", "merge", "print"] {
+        let ident = syn::Ident::new(field, proc_macro2::Span::call_site());
+        let found = bindings(parse_quote! {
+            #[binding(prefix = "acme")]
+            pub struct Foo<'a> {
+                /// sbc: len = 8
+                #ident: &'a str,
+            }
+        });
+        let err = render(&found, "FOO_HPP", "foo.h", &Features::default()).unwrap_err();
+        assert!(err.to_string().contains("clashes with a C++ method"));
+    }
+}
@@ -0,0 +1,154 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use crate::attributes::Features;
+use crate::header::{render, scan, Binding};
+use syn::parse_quote;
+
+fn bindings(file: syn::File) -> Vec<Binding> {
+    let mut found = Vec::new();
+    scan(&file.items, &mut found).unwrap();
+    found
+}
+
+#[test]
+fn can_render_header() {
+    let found = bindings(parse_quote! {
+        #[binding(prefix = "acme")]
+        pub struct Foo<'a> {
+            id: u8,
+            /// sbc: len = 8
+            name: &'a str,
+            bar: Bar<'a>,
+            bars: [Bar<'a>; 2],
+        }
+        mod nested {
+            #[serde_bindgen_core::binding(prefix = "acme", skip = ["print_chunk", "parse_feed"])]
+            pub struct Bar<'a> {
+                /// sbc: len = 4
+                tags: [&'a str; 3],
+                grid: [[i16; 3]; 2],
+            }
+        }
+        pub struct Ignored {
+            id: u8,
+        }
+    });
+    let header = render(&found, "FOO_H", &Features(Features::ALL.to_vec())).unwrap();
+    assert!(header.contains("#ifndef FOO_H\n#define FOO_H\n"));
+    assert!(header.contains("#define BAR_MAX_LEN "));
+    assert!(header.contains("#define FOO_MAX_LEN ("));
+    assert!(header.contains("#define BAR_CBOR_MAX_LEN 45\n"));
+    assert!(header.contains("#define BAR_MSGPACK_MAX_LEN 45\n"));
+    assert!(header
+        .contains("#define FOO_CBOR_MAX_LEN (29 + BAR_CBOR_MAX_LEN * 1 + BAR_CBOR_MAX_LEN * 2)\n"));
+    assert!(header.contains("typedef struct FooBorrowed {\n    FooView opaque;\n} FooBorrowed;"));
+    let view = "typedef struct FooView {\n    uint8_t id;\n    StrView name;\n    BarView bar;\n    BarView bars[2];\n} FooView;";
+    assert!(header.find("typedef struct BarView {").unwrap() < header.find(view).unwrap());
+    assert!(header.find("typedef struct StrView {").unwrap() < header.find(view).unwrap());
+    assert!(header.contains(
+        "_Static_assert(sizeof(FooView) == (sizeof(void *) == 8 ? 216 : 120), \"FooView size\");"
+    ));
+    assert!(header.contains("_Static_assert(offsetof(FooView, bar) == (sizeof(void *) == 8 ? 24 : 12), \"FooView.bar offset\");"));
+    assert!(header.contains("_Static_assert(offsetof(BarView, grid) == (sizeof(void *) == 8 ? 48 : 24), \"BarView.grid offset\");"));
+    assert!(header
+        .contains("} ChunkState;\n_Static_assert(sizeof(ChunkState) == 40, \"ChunkState size\");"));
+    assert!(header.contains("typedef struct FeedState {\n    uint16_t opaque[39];\n} FeedState;"));
+    assert!(!header.contains("typedef struct FooBorrowed FooBorrowed;"));
+    assert!(!header.contains("typedef struct FooOwned FooOwned;"));
+    let bar =
+        "typedef struct BarOwned {\n    char tags[3][4];\n    int16_t grid[2][3];\n} BarOwned;";
+    let foo = "typedef struct FooOwned {\n    uint8_t id;\n    char name[8];\n    BarOwned bar;\n    BarOwned bars[2];\n} FooOwned;";
+    assert!(header.find(bar).unwrap() < header.find(foo).unwrap());
+    assert!(header.contains("_Static_assert(sizeof(BarOwned) == 24, \"BarOwned size\");"));
+    assert!(header
+        .contains("_Static_assert(offsetof(BarOwned, grid) == 12, \"BarOwned.grid offset\");"));
+    assert!(header.contains("_Static_assert(sizeof(FooOwned) == 82, \"FooOwned size\");"));
+    assert!(
+        header.contains("_Static_assert(offsetof(FooOwned, bar) == 10, \"FooOwned.bar offset\");")
+    );
+    assert!(header
+        .contains("_Static_assert(offsetof(FooOwned, bars) == 34, \"FooOwned.bars offset\");"));
+    assert!(header.contains("#define BAR_PB_MAX_LEN 35\n"));
+    assert!(header.contains("#define FOO_PB_MAX_LEN 123\n"));
+    assert!(header.contains("#define FOO_ABI_HASH 0x"));
+    assert!(header.contains("uint64_t acme_abi_hash_foo(void);"));
+    assert!(header
+        .contains("int32_t acme_pb_encode_foo(const FooOwned *src, uint8_t *bytes, size_t *len);"));
+    assert!(header.contains(
+        "int32_t acme_cbor_print_foo(const FooOwned *src, uint8_t *bytes, size_t *len);"
+    ));
+    assert!(header
+        .contains("int32_t acme_pb_decode_foo(FooOwned *dst, const uint8_t *bytes, size_t len);"));
+    assert!(header.contains("void acme_init_foo(FooOwned *dst);"));
+    assert!(header
+        .contains("int32_t acme_parse_foo(FooBorrowed *dst, const uint8_t *bytes, size_t len);"));
+    assert!(
+        header.contains("uint64_t acme_changed_bar(const BarOwned *old, const BarOwned *new_);")
+    );
+    assert!(!header.contains("acme_print_chunk_bar"));
+    assert!(!header.contains("Ignored"));
+    let header = render(&found, "FOO_H", &Features::default()).unwrap();
+    assert!(!header.contains("PB_MAX_LEN"));
+    assert!(!header.contains("acme_pb_encode_foo"));
+    assert!(!header.contains("acme_cbor_print_foo"));
+}
+
+#[test]
+fn can_render_header_aliases() {
+    let found = bindings(parse_quote! {
+        #[binding(prefix = "acme", owned = "acme_foo_t", borrowed = "acme_foo_ref_t", only = ["init", "copy"])]
+        pub struct Foo<'a> {
+            /// sbc: len = 8
+            name: &'a str,
+        }
+    });
+    let header = render(&found, "FOO_H", &Features::default()).unwrap();
+    assert!(header.contains("typedef struct acme_foo_t {\n    char name[8];\n} acme_foo_t;"));
+    assert!(
+        header.contains("typedef struct acme_foo_ref_t {\n    FooView opaque;\n} acme_foo_ref_t;")
+    );
+    assert!(!header.contains("ChunkState"));
+    assert!(header.contains("void acme_copy_foo(acme_foo_t *dst, const acme_foo_ref_t *src);"));
+    assert!(!header.contains("FooOwned"));
+}
+
+#[test]
+fn can_render_header_errors() {
+    let unknown = bindings(parse_quote! {
+        #[binding]
+        pub struct Foo<'a> {
+            bar: Bar<'a>,
+        }
+    });
+    assert!(render(&unknown, "FOO_H", &Features::default()).is_err());
+    let twice = bindings(parse_quote! {
+        #[binding]
+        pub struct Foo {
+            id: u8,
+        }
+        mod nested {
+            #[binding]
+            pub struct Foo {
+                id: u8,
+            }
+        }
+    });
+    assert!(render(&twice, "FOO_H", &Features::default()).is_err());
+}
@@ -0,0 +1,703 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+mod attribute;
+mod cpp;
+mod field;
+mod header;
+mod naming;
+mod path;
+mod proto;
+mod python;
+mod schema;
+mod typescript;
+
+use crate::attributes::RenameRule;
+use crate::context::{
+    Context, ImplAbiHash, ImplCopyFrom, ImplDefault, ImplFromRef, ImplFromView, ImplInit,
+    ImplLayout,
+};
+use crate::field::Field;
+use crate::naming::Naming;
+use std::matches;
+use syn::parse_quote;
+
+#[test]
+fn can_parse_struct() {
+    let s: Context = parse_quote!(
+        pub struct Foo<'a> {
+            pub id_0: i8,
+            /// sbc: default = 3
+            pub id_1: i16,
+            pub id_2: i32,
+            pub id_3: u8,
+            pub id_4: u16,
+            pub id_5: u32,
+            pub id_6: bool,
+            pub id_7: Foo<'a>,
+            pub id_8: bar::Foo,
+            pub id_9: ::Moo<'a>,
+            /// garbage
+            /// sbc: default = "hello world"
+            /// more garbage
+            /// sbc: len = 12
+            /// ignore me
+            pub id_10: &'a str,
+            pub id_11: [bool; 6],
+            pub id_12: [Foo; 5],
+            pub id_13: [::bar::Foo<'a>; 8],
+            // pub id_14: [&'a str; 3],
+        }
+    );
+    // Validate struct
+    assert_eq!(s.path.ident, "Foo");
+    assert!(s.tok_vis.is_some());
+    assert!(s.path.generics.is_some());
+
+    // Validate fields (We use alias to make test "easier" to read)
+    // use super::field::FieldType::Array as AR;
+    use super::field::FieldType::Primative as PR;
+    use super::field::FieldType::RefStr as RS;
+    use super::field::FieldType::Struct as NS;
+    // use super::field::FieldTypeArray::Primative as FAPR;
+    // use super::field::FieldTypeArray::Struct as FANS;
+
+    let f: Vec<Field> = s.fields.into_iter().collect();
+    assert!(matches!(&f[0].ty, PR(ident) if ident=="i8"));
+    assert!(matches!(&f[1].ty, PR(ident) if ident=="i16"));
+    assert!(matches!(&f[2].ty, PR(ident) if ident=="i32"));
+    assert!(matches!(&f[3].ty, PR(ident) if ident=="u8"));
+    assert!(matches!(&f[4].ty, PR(ident) if ident=="u16"));
+    assert!(matches!(&f[5].ty, PR(ident) if ident=="u32"));
+    assert!(matches!(&f[6].ty, PR(ident) if ident=="bool"));
+    assert!(matches!(&f[7].ty, NS(p) if p.ident=="Foo" && p.generics.is_some()));
+    assert!(matches!(&f[8].ty, NS(p) if p.ident=="Foo" && p.generics.is_none()));
+    assert!(matches!(&f[9].ty, NS(p) if p.ident=="Moo" && p.generics.is_some()));
+    assert!(matches!(&f[10].ty, RS(..)));
+    /*
+    assert!(matches!(&f[11].ty, AR(FAPR(_,path,l)) if path.ident=="bool" && lit_is!(l,6)));
+    assert!(
+        matches!(&f[12].ty, AR(FANS(_,p,l)) if p.generics.is_none() && p.ident=="Foo" && lit_is!(l,5))
+    );
+    assert!(
+        matches!(&f[13].ty, AR(FANS(_,p,l)) if p.generics.is_some() && p.ident=="Foo" && lit_is!(l,8))
+    );
+    */
+
+    // TODO - read default and len on id_10
+
+    assert_eq!(f[0].ident, "id_0");
+    assert_eq!(f[1].ident, "id_1");
+    assert_eq!(f[2].ident, "id_2");
+    assert_eq!(f[3].ident, "id_3");
+    assert_eq!(f[4].ident, "id_4");
+    assert_eq!(f[5].ident, "id_5");
+    assert_eq!(f[6].ident, "id_6");
+    assert_eq!(f[7].ident, "id_7");
+    assert_eq!(f[8].ident, "id_8");
+    assert_eq!(f[9].ident, "id_9");
+    assert_eq!(f[10].ident, "id_10");
+    assert_eq!(f[11].ident, "id_11");
+    assert_eq!(f[12].ident, "id_12");
+    assert_eq!(f[13].ident, "id_13");
+}
+
+#[test]
+fn can_to_tokens_struct() {
+    let s: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: len = 22
+            id_0: &'a str,
+            id_1: Bar,
+            id_2: ::Baz<'a>,
+            id_3: [::Baz<'a>; 10],
+        }
+    );
+    let expect = quote::quote! {
+        pub struct Foo <'a> {
+            id_0: &'a str,
+            id_1: Bar,
+            id_2: ::Baz<'a>,
+            id_3: [::Baz<'a>; 10],
+        }
+    };
+    let quoted = quote::quote! {#s};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_as_owned() {
+    let mut original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: len = 22
+            id_0: &'a str,
+            id_1: Bar,
+            id_2: ::Baz<'a>,
+            id_3: [::Baz<'a>; 10],
+            id_4: i32,
+            id_5: u16,
+        }
+    );
+    let expect = quote::quote! {
+        pub struct FooOwned {
+            #[serde(with = "serde_bindgen_core::owned_str")]
+            id_0: [u8; 22],
+            id_1: BarOwned,
+            id_2: ::BazOwned,
+            id_3: [::BazOwned; 10],
+            id_4: i32,
+            id_5: u16,
+        }
+    };
+    original.as_owned();
+    let quoted = quote::quote! {#original};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_as_view() {
+    let mut original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: len = 22
+            id_0: &'a str,
+            id_1: Bar,
+            id_2: ::Baz<'a>,
+            id_3: [::Baz<'a>; 10],
+            id_4: [&'a str; 2],
+            id_5: u16,
+        }
+    );
+    let expect = quote::quote! {
+        pub struct FooView {
+            id_0: serde_bindgen_core::StrView,
+            id_1: BarView,
+            id_2: ::BazView,
+            id_3: [::BazView; 10],
+            id_4: [serde_bindgen_core::StrView; 2],
+            id_5: u16,
+        }
+    };
+    original.as_view();
+    let quoted = quote::quote! {#original};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_partial() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: len = 22
+            id_0: &'a str,
+            id_1: Bar,
+            id_2: ::Baz<'a>,
+            id_3: [::Baz<'a>; 10],
+            id_4: [u8; 2],
+            id_5: u16,
+        }
+    );
+    let expect = quote::quote! {
+        pub struct FooPartial<'a> {
+            #[serde(borrow)] id_0: Option<&'a str>,
+            id_1: Option<BarPartial>,
+            #[serde(borrow)] id_2: Option<::BazPartial<'a> >,
+            #[serde(borrow)] id_3: Option<[::Baz<'a>; 10]>,
+            id_4: Option<[u8; 2]>,
+            id_5: Option<u16>
+        }
+    };
+    let partial = original.partial();
+    let quoted = quote::quote! {#partial};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_default() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: default = "hello"
+            /// sbc: len = 22
+            id_0: &'a str,
+            id_1: Bar,
+            id_2: ::Baz<'a>,
+            id_3: [::Baz<'a>; 2],
+            /// sbc: default = 3
+            id_4: i32,
+            /// sbc: default = 6
+            id_5: u16,
+        }
+    );
+    let expect = quote::quote!(
+        impl Default for FooOwned {
+            fn default() -> FooOwned {
+                FooOwned {
+                    id_0: serde_bindgen_core::SafeCopy::safe_copy(&"hello"),
+                    id_1: Default::default(),
+                    id_2: Default::default(),
+                    id_3: [Default::default(),Default::default()],
+                    id_4: 3,
+                    id_5: 6
+                }
+            }
+        }
+    );
+    let impl_default = ImplDefault::new(&original.path, &original.fields);
+    let quoted = quote::quote! {#impl_default};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_init() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: default = "hello"
+            /// sbc: len = 22
+            id_0: &'a str,
+            id_1: Bar,
+            id_2: [[u8; 2]; 2],
+            /// sbc: default = 3
+            id_3: i32,
+        }
+    );
+    let expect = quote::quote! {
+        impl serde_bindgen_core::in_place::Init for FooOwned {
+            fn init(&mut self) {
+                serde_bindgen_core::in_place::copy_str(&mut self.id_0, "hello");
+                serde_bindgen_core::in_place::Init::init(&mut self.id_1);
+                for item in self.id_2.iter_mut() {
+                    for item in (*item).iter_mut() {
+                        (*item) = 0;
+                    }
+                }
+                self.id_3 = 3;
+            }
+        }
+    };
+    let impl_init = ImplInit::new(&original.path, &original.fields);
+    let quoted = quote::quote! {#impl_init};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_layout() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: len = 22
+            id_0: &'a str,
+            id_1: Bar<'a>,
+            id_2: [[u16; 2]; 3],
+        }
+    );
+    let expect = quote::quote! {
+        pub const FOO_LAYOUT: serde_bindgen_core::layout::Layout<3usize> =
+            serde_bindgen_core::layout::Layout::new([
+                (22usize, 1usize),
+                (BAR_LAYOUT.size, BAR_LAYOUT.align),
+                {
+                    let (size, align) = {
+                        let (size, align) = (2usize, 2usize);
+                        (size * 2usize, align)
+                    };
+                    (size * 3usize, align)
+                }
+            ]);
+        const _: () = assert!(core::mem::size_of::<FooOwned>() == FOO_LAYOUT.size);
+        const _: () = assert!(core::mem::align_of::<FooOwned>() == FOO_LAYOUT.align);
+        const _: () = assert!(core::mem::offset_of!(FooOwned, id_0) == FOO_LAYOUT.offsets[0usize]);
+        const _: () = assert!(core::mem::offset_of!(FooOwned, id_1) == FOO_LAYOUT.offsets[1usize]);
+        const _: () = assert!(core::mem::offset_of!(FooOwned, id_2) == FOO_LAYOUT.offsets[2usize]);
+    };
+    let impl_layout = ImplLayout::new(&original.path, &original.fields);
+    let quoted = quote::quote! {#impl_layout};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_abi_hash() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: len = 22
+            id_0: [&'a str; 2],
+            id_1: [Bar<'a>; 3],
+            id_2: u16,
+        }
+    );
+    let expect = quote::quote! {
+        pub const FOO_ABI_HASH: u64 = serde_bindgen_core::layout::AbiHash::new()
+            .str("id_0:[str22;2]")
+            .str("id_1:[_;3]")
+            .u64(BAR_ABI_HASH)
+            .str("id_2:u16")
+            .layout(&FOO_LAYOUT)
+            .finish();
+    };
+    let impl_abi_hash = ImplAbiHash::new(&original.path, &original.fields);
+    let quoted = quote::quote! {#impl_abi_hash};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_binary_weight() {
+    let mut original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: len = 30
+            host_name: [&'a str; 2],
+            bar: Bar<'a>,
+            bars: [Bar<'a>; 2],
+            level: i16,
+        }
+    );
+    original.rename_all = Some(RenameRule::Camel);
+    let expect = quote::quote! {
+        pub const FOO_CBOR_MAX_LEN: usize = 92usize + BAR_CBOR_MAX_LEN * 1usize + BAR_CBOR_MAX_LEN * 2usize;
+    };
+    let impl_cbor_weight = original.impl_cbor_weight();
+    let quoted = quote::quote! {#impl_cbor_weight};
+    assert_eq!(expect.to_string(), quoted.to_string());
+
+    // a 29 byte string is a fixstr in MessagePack, but not in CBOR
+    let expect = quote::quote! {
+        pub const FOO_MSGPACK_MAX_LEN: usize = 90usize + BAR_MSGPACK_MAX_LEN * 1usize + BAR_MSGPACK_MAX_LEN * 2usize;
+    };
+    let impl_msgpack_weight = original.impl_msgpack_weight();
+    let quoted = quote::quote! {#impl_msgpack_weight};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_protobuf() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            /// sbc: len = 4
+            id_0: [&'a str; 2],
+            /// sbc: tag = 200
+            id_1: Bar<'a>,
+            id_2: [[i16; 3]; 2],
+            id_3: u8,
+        }
+    );
+    let expect = quote::quote! {
+        pub const FOO_PB_MAX_LEN: usize = 33usize
+            + serde_bindgen_core::protobuf::delimited_len(2usize, BAR_PB_MAX_LEN) * 1usize;
+        impl serde_bindgen_core::protobuf::Message for FooOwned {
+            fn encode(
+                &self,
+                enc: &mut serde_bindgen_core::protobuf::Encoder<'_>,
+            ) -> serde_bindgen_core::protobuf::Result<()> {
+                enc.strings(1u32, &self.id_0)?;
+                enc.message(200u32, &self.id_1)?;
+                enc.packed(3u32, self.id_2.as_flattened())?;
+                enc.scalar(4u32, self.id_3)?;
+                Ok(())
+            }
+
+            fn decode(
+                &mut self,
+                dec: &mut serde_bindgen_core::protobuf::Decoder<'_>,
+            ) -> serde_bindgen_core::protobuf::Result<()> {
+                let mut count_id_0 = 0usize;
+                let mut count_id_2 = 0usize;
+                while let Some((tag, wire)) = dec.key()? {
+                    match tag {
+                        1u32 => dec.strings(wire, &mut self.id_0, &mut count_id_0)?,
+                        200u32 => dec.message(wire, &mut self.id_1)?,
+                        3u32 => dec.packed(wire, self.id_2.as_flattened_mut(), &mut count_id_2)?,
+                        4u32 => dec.scalar(wire, &mut self.id_3)?,
+                        _ => dec.skip(wire)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+    let impl_protobuf = original.impl_protobuf().unwrap();
+    let quoted = quote::quote! {#impl_protobuf};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_check_protobuf_tags() {
+    let original: Context = parse_quote!(
+        pub struct Foo {
+            /// sbc: tag = 2
+            id_0: u8,
+            id_1: u8,
+        }
+    );
+    assert!(original.pb_tags().is_err());
+    let original: Context = parse_quote!(
+        pub struct Foo {
+            /// sbc: tag = 19000
+            id_0: u8,
+        }
+    );
+    assert!(original.pb_tags().is_err());
+    let original: Context = parse_quote!(
+        pub struct Foo {
+            id_0: u8,
+            /// sbc: tag = 9
+            id_1: u8,
+            id_2: u8,
+        }
+    );
+    assert_eq!(original.pb_tags().unwrap(), vec![1, 9, 3]);
+}
+
+#[test]
+fn can_impl_copy_from() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            id_0: &'a str,
+            id_1: Bar<'a>,
+            id_2: [&'a str; 2],
+            id_3: i32,
+        }
+    );
+    let expect = quote::quote! {
+        impl<'a> serde_bindgen_core::in_place::CopyFrom<FooBorrowed<'a> > for FooOwned {
+            fn copy_from(&mut self, s: &FooBorrowed<'a>) {
+                serde_bindgen_core::in_place::copy_str(&mut self.id_0, s.id_0);
+                serde_bindgen_core::in_place::CopyFrom::copy_from(&mut self.id_1, &s.id_1);
+                for (item, src) in self.id_2.iter_mut().zip(s.id_2.iter()) {
+                    serde_bindgen_core::in_place::copy_str(&mut (*item), (*src));
+                }
+                self.id_3 = s.id_3;
+            }
+        }
+    };
+    let impl_copy_from = ImplCopyFrom::new(&original.path, &original.fields);
+    let quoted = quote::quote! {#impl_copy_from};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_default_large_array() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            id_0: [u8; 1024],
+            id_1: [[&'a str; 17]; 2],
+        }
+    );
+    let expect = quote::quote! {
+        impl Default for FooOwned {
+            fn default() -> FooOwned {
+                FooOwned {
+                    id_0: core::array::from_fn(|_| 0),
+                    id_1: [
+                        core::array::from_fn(|_| serde_bindgen_core::SafeCopy::safe_copy(&"")),
+                        core::array::from_fn(|_| serde_bindgen_core::SafeCopy::safe_copy(&""))
+                    ]
+                }
+            }
+        }
+    };
+    let impl_default = ImplDefault::new(&original.path, &original.fields);
+    let quoted = quote::quote! {#impl_default};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_from_large_array() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            id0: [[&'a str; 20]; 20],
+        }
+    );
+    let expect = quote::quote! {
+        impl<'a> From<&FooBorrowed<'a> > for FooOwned {
+            fn from(s: &FooBorrowed<'a>) -> FooOwned {
+                FooOwned {
+                    id0: core::array::from_fn(|i1| core::array::from_fn(|i0| serde_bindgen_core::SafeCopy::safe_copy(&s.id0[i1][i0])))
+                }
+            }
+        }
+    };
+    let impl_from = ImplFromRef::new(&original.path, &original.fields);
+    let quoted = quote::quote! {#impl_from};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_from() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            id0: &'a str,
+            id1: [u8; 2],
+            id2: Baz<'a>,
+            id3: [Baz<'a>; 2],
+        }
+    );
+    #[rustfmt::skip]
+    let expect: syn::ItemImpl = parse_quote!(
+        impl<'a> From<&FooBorrowed<'a>> for FooOwned {
+            fn from(s: &FooBorrowed<'a>) -> FooOwned {
+                FooOwned {
+                    id0: serde_bindgen_core::SafeCopy::safe_copy(&s.id0),
+                    id1: [s.id1[0],s.id1[1]],
+                    id2: From::from(&s.id2),
+                    id3: [From::from(&s.id3[0]), From::from(&s.id3[1])]
+                }
+            }
+        }
+    );
+    let impl_from = ImplFromRef::new(&original.path, &original.fields);
+    let expect = quote::quote! {#expect};
+    let quoted = quote::quote! {#impl_from};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_from_view() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            id0: &'a str,
+            id1: [u8; 2],
+            id2: Baz<'a>,
+            id3: [&'a str; 2],
+        }
+    );
+    #[rustfmt::skip]
+    let expect: syn::ItemImpl = parse_quote!(
+        impl<'a> serde_bindgen_core::FromView<'a, FooView> for FooBorrowed<'a> {
+            unsafe fn from_view(s: &'a FooView) -> Result<FooBorrowed<'a>, core::str::Utf8Error> {
+                Ok(FooBorrowed {
+                    id0: serde_bindgen_core::FromView::from_view(&s.id0)?,
+                    id1: s.id1,
+                    id2: serde_bindgen_core::FromView::from_view(&s.id2)?,
+                    id3: serde_bindgen_core::FromView::from_view(&s.id3)?
+                })
+            }
+        }
+    );
+    let impl_from = ImplFromView::new(&original.path, &original.fields);
+    let expect = quote::quote! {#expect};
+    let quoted = quote::quote! {#impl_from};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_impl_binding() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            item: &'a str,
+        }
+    );
+    let binding = original.impl_binding();
+    let expect = quote::quote! {
+        impl serde_bindgen_core::Binding for FooOwned {
+            type Owned = FooOwned;
+            type Borrowed<'a> = FooBorrowed<'a>;
+            const MAX_LEN: usize = FOO_MAX_LEN;
+            fn to_owned(data: &Self::Borrowed<'_>) -> FooOwned {
+                From::from(data)
+            }
+        }
+    };
+    let quoted = quote::quote! {#binding};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_binding_default() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            item: &'a str,
+        }
+    );
+    let naming = Naming::new("foo");
+    let binding = original.binding_init(&naming);
+    let expect = quote::quote! {
+        #[no_mangle]
+        pub extern "C" fn foo_init_foo<'a>(dst: &mut FooOwned) {
+            serde_bindgen_core::in_place::Init::init(dst);
+        }
+    };
+    let quoted = quote::quote! {#binding};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_binding_copy() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            item: &'a str,
+        }
+    );
+    let naming = Naming::new("foo");
+    let binding = original.binding_copy(&naming);
+    let expect = quote::quote! {
+        #[no_mangle]
+        pub extern "C" fn foo_copy_foo<'a>(dst: &mut FooOwned, src: &FooBorrowed<'a>) {
+            serde_bindgen_core::in_place::CopyFrom::copy_from(dst, src);
+        }
+    };
+    let quoted = quote::quote! {#binding};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_binding_parse() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            item: &'a str,
+        }
+    );
+    let naming = Naming::new("foo");
+    let binding = original.binding_parse(&naming);
+    let expect = quote::quote! {
+        #[no_mangle]
+        pub unsafe extern "C" fn foo_parse_foo<'a>(dst: &mut FooBorrowed<'a>, bytes: *const u8, len: usize) -> i32 {
+            let slice = core::slice::from_raw_parts(bytes,len);
+            match serde_bindgen_core::serde_json_core::from_slice(&slice) {
+                Ok((item,len)) => {
+                    *dst = item;
+                    len as i32
+                },
+                Err(_) => -1
+            }
+        }
+    };
+    let quoted = quote::quote! {#binding};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
+
+#[test]
+fn can_binding_print() {
+    let original: Context = parse_quote!(
+        pub struct Foo<'a> {
+            item: &'a str,
+        }
+    );
+    let naming = Naming::new("foo");
+    let binding = original.binding_print(&naming);
+    let expect = quote::quote! {
+        #[no_mangle]
+        pub unsafe extern "C" fn foo_print_foo_borrowed<'a>(data: &FooBorrowed<'a>, bytes: *mut u8, len: &mut usize) -> i32 {
+            let mut slice = core::slice::from_raw_parts_mut(bytes,*len);
+            match serde_bindgen_core::serde_json_core::to_slice(data, &mut slice) {
+                Ok(l) => {
+                    *len = l;
+                    0
+                },
+                Err(_) => -1
+            }
+        }
+    };
+    let quoted = quote::quote! {#binding};
+    assert_eq!(expect.to_string(), quoted.to_string());
+}
@@ -0,0 +1,106 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use crate::attributes::ContainerAttributes;
+use crate::naming::{Form, Naming};
+use crate::path::PathNamed;
+use syn::parse_quote;
+
+#[test]
+fn can_name_symbol_default() {
+    let naming = Naming::new("sbc");
+    let path: PathNamed = parse_quote!(FooBar<'a>);
+    assert_eq!(
+        naming.symbol("parse", Form::Original, &path),
+        "sbc_parse_foo_bar"
+    );
+    assert_eq!(
+        naming.symbol("print", Form::Owned, &path),
+        "sbc_print_foo_bar_owned"
+    );
+    assert_eq!(
+        naming.variant_symbol("print", "chunk", Form::Owned, &path),
+        "sbc_print_foo_bar_owned_chunk"
+    );
+}
+
+#[test]
+fn can_name_symbol_template() {
+    let attrs: ContainerAttributes =
+        parse_quote!(prefix = "acme", symbol = "{prefix}_{type}_{verb}");
+    let naming = Naming::from_container(&attrs).unwrap();
+    let path: PathNamed = parse_quote!(Foo<'a>);
+    assert_eq!(
+        naming.symbol("parse", Form::Original, &path),
+        "acme_foo_parse"
+    );
+    assert_eq!(
+        naming.variant_symbol("parse", "feed", Form::Original, &path),
+        "acme_foo_parse_feed"
+    );
+
+    let attrs: ContainerAttributes = parse_quote!(symbol = "{type}_{verb}{variant}_{prefix}");
+    let naming = Naming::from_container(&attrs).unwrap();
+    assert_eq!(
+        naming.variant_symbol("print", "chunk", Form::View, &path),
+        "foo_view_print_chunk_sbc"
+    );
+
+    let attrs: ContainerAttributes = parse_quote!(symbol = "{prefix}_{verb}");
+    assert!(Naming::from_container(&attrs).is_err());
+}
+
+#[test]
+fn can_name_aliases() {
+    let attrs: ContainerAttributes =
+        parse_quote!(owned = "acme_foo_t", borrowed = "acme_foo_ref_t");
+    let naming = Naming::from_container(&attrs).unwrap();
+    let path: PathNamed = parse_quote!(Foo<'a>);
+    let expect = quote::quote! {
+        #[allow(non_camel_case_types)]
+        pub type acme_foo_t = FooOwned;
+        #[allow(non_camel_case_types)]
+        pub type acme_foo_ref_t<'a> = FooBorrowed<'a>;
+    };
+    assert_eq!(expect.to_string(), naming.aliases(&path).to_string());
+}
+
+#[test]
+fn can_name_symbol_aliases() {
+    let attrs: ContainerAttributes = parse_quote!(
+        symbol = "{type}_{verb}",
+        owned = "acme_foo_t",
+        borrowed = "AcmeFooRef"
+    );
+    let naming = Naming::from_container(&attrs).unwrap();
+    let path: PathNamed = parse_quote!(Foo<'a>);
+    assert_eq!(naming.symbol("init", Form::Original, &path), "foo_init");
+    assert_eq!(
+        naming.symbol("print", Form::Owned, &path),
+        "acme_foo_t_print"
+    );
+    assert_eq!(
+        naming.symbol("print", Form::Borrowed, &path),
+        "acme_foo_ref_print"
+    );
+    assert_eq!(
+        naming.variant_symbol("print", "chunk", Form::Owned, &path),
+        "acme_foo_t_print_chunk"
+    );
+}
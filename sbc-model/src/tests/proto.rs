@@ -0,0 +1,87 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use crate::header::{scan, Binding};
+use crate::proto::render;
+use syn::parse_quote;
+
+fn bindings(file: syn::File) -> Vec<Binding> {
+    let mut found = Vec::new();
+    scan(&file.items, &mut found).unwrap();
+    found
+}
+
+#[test]
+fn can_render_proto() {
+    let found = bindings(parse_quote! {
+        #[binding]
+        pub struct Bar {
+            on: bool,
+            level: i32,
+        }
+
+        /// A thing
+        #[binding(prefix = "acme")]
+        pub struct Foo<'a> {
+            /// The name
+            /// sbc: len = 8
+            name: &'a str,
+            /// sbc: tag = 10
+            bar: Bar,
+            grid: [[i16; 3]; 2],
+            /// sbc: len = 4
+            tags: [&'a str; 2],
+            id: u8,
+        }
+    });
+    let expect = r#"// Generated by sbc-gen, do not edit
+syntax = "proto3";
+
+import "nanopb.proto";
+
+message Bar {
+  bool on = 1;
+  sint32 level = 2;
+}
+
+// A thing
+message Foo {
+  // The name
+  string name = 1 [(nanopb).max_size = 8];
+  Bar bar = 10;
+  repeated sint32 grid = 3 [(nanopb).int_size = IS_16, (nanopb).max_count = 6, (nanopb).fixed_count = true];
+  repeated string tags = 4 [(nanopb).max_size = 4, (nanopb).max_count = 2, (nanopb).fixed_count = true];
+  uint32 id = 5 [(nanopb).int_size = IS_8];
+}
+"#;
+    assert_eq!(render(&found).unwrap(), expect);
+}
+
+#[test]
+fn can_render_proto_errors() {
+    let found = bindings(parse_quote! {
+        #[binding]
+        pub struct Foo {
+            /// sbc: tag = 2
+            a: u8,
+            b: u8,
+        }
+    });
+    assert!(render(&found).is_err());
+}
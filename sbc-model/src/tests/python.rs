@@ -0,0 +1,101 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use crate::attributes::Features;
+use crate::header::{scan, Binding};
+use crate::python::render;
+use syn::parse_quote;
+
+fn bindings(file: syn::File) -> Vec<Binding> {
+    let mut found = Vec::new();
+    scan(&file.items, &mut found).unwrap();
+    found
+}
+
+#[test]
+fn can_render_python() {
+    let found = bindings(parse_quote! {
+        /// A thing
+        #[binding(prefix = "acme", rename_all = "camelCase", only = ["init", "print_owned", "abi_hash"])]
+        pub struct Foo<'a> {
+            /// sbc: len = 8
+            host_name: &'a str,
+            /// sbc: len = 4
+            tags: [&'a str; 2],
+            bars: [Bar<'a>; 2],
+        }
+        #[binding(prefix = "acme", owned = "acme_bar_t", only = ["copy"])]
+        pub struct Bar<'a> {
+            grid: [[u16; 3]; 2],
+            level: i8,
+        }
+    });
+    let module = render(&found, &Features::default()).unwrap();
+    let bar = r#"
+class acme_bar_t(ctypes.Structure):
+    _fields_ = [
+        ("grid", (ctypes.c_uint16 * 3) * 2),
+        ("level", ctypes.c_int8),
+    ]
+    _keys_ = [
+        ("grid", "grid"),
+        ("level", "level"),
+    ]
+
+
+assert ctypes.sizeof(acme_bar_t) == 14
+assert acme_bar_t.grid.offset == 0
+assert acme_bar_t.level.offset == 12
+"#;
+    let foo = r#"
+class FooOwned(ctypes.Structure):
+    """A thing"""
+
+    _fields_ = [
+        ("host_name", ctypes.c_char * 8),
+        ("tags", (ctypes.c_char * 4) * 2),
+        ("bars", acme_bar_t * 2),
+    ]
+    _keys_ = [
+        ("host_name", "hostName"),
+        ("tags", "tags"),
+        ("bars", "bars"),
+    ]
+"#;
+    assert!(module.find(bar).unwrap() < module.find(foo).unwrap());
+    assert!(module.contains("\nBAR_MAX_LEN = "));
+    assert!(module.contains("\nFOO_ABI_HASH = 0x"));
+    let load = r#"
+def load(path):
+    """Open the library and declare its bindings"""
+    lib = ctypes.CDLL(path)
+    lib.acme_init_foo.argtypes = [ctypes.POINTER(FooOwned)]
+    lib.acme_init_foo.restype = None
+    lib.acme_abi_hash_foo.argtypes = []
+    lib.acme_abi_hash_foo.restype = ctypes.c_uint64
+    lib.acme_print_foo_owned.argtypes = [ctypes.POINTER(FooOwned), ctypes.POINTER(ctypes.c_uint8), ctypes.POINTER(ctypes.c_size_t)]
+    lib.acme_print_foo_owned.restype = ctypes.c_int32
+    if lib.acme_abi_hash_foo() != FOO_ABI_HASH:
+        raise ImportError("%s was built from a different Foo" % path)
+    lib.acme_copy_bar.argtypes = [ctypes.POINTER(acme_bar_t), ctypes.c_void_p]
+    lib.acme_copy_bar.restype = None
+    return lib
+"#;
+    assert!(module.ends_with(load));
+}
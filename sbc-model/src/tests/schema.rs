@@ -0,0 +1,153 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use crate::header::{scan, Binding};
+use crate::schema::{default_value, render, schema, Json};
+use syn::parse_quote;
+
+fn bindings(file: syn::File) -> Vec<Binding> {
+    let mut found = Vec::new();
+    scan(&file.items, &mut found).unwrap();
+    found
+}
+
+fn object(members: Vec<(&str, Json)>) -> Json {
+    Json::Object(
+        members
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+fn str(s: &str) -> Json {
+    Json::Str(s.to_string())
+}
+
+#[test]
+fn can_render_schema() {
+    let found = bindings(parse_quote! {
+        /// A thing
+        #[binding(rename_all = "camelCase")]
+        pub struct Foo<'a> {
+            /// The name
+            /// sbc: len = 8
+            /// sbc: default = "foo"
+            host_name: &'a str,
+            /// sbc: default = -3
+            level: i8,
+            grid: [[u16; 3]; 2],
+            bars: [Bar<'a>; 2],
+        }
+    });
+    let expect = object(vec![
+        ("type", str("object")),
+        ("description", str("A thing")),
+        (
+            "properties",
+            object(vec![
+                (
+                    "hostName",
+                    object(vec![
+                        ("description", str("The name")),
+                        ("type", str("string")),
+                        ("maxLength", Json::Int(7)),
+                        ("default", str("foo")),
+                    ]),
+                ),
+                (
+                    "level",
+                    object(vec![
+                        ("type", str("integer")),
+                        ("minimum", Json::Int(-128)),
+                        ("maximum", Json::Int(127)),
+                        ("default", Json::Int(-3)),
+                    ]),
+                ),
+                (
+                    "grid",
+                    object(vec![
+                        ("type", str("array")),
+                        (
+                            "items",
+                            object(vec![
+                                ("type", str("array")),
+                                (
+                                    "items",
+                                    object(vec![
+                                        ("type", str("integer")),
+                                        ("minimum", Json::Int(0)),
+                                        ("maximum", Json::Int(65535)),
+                                    ]),
+                                ),
+                                ("minItems", Json::Int(3)),
+                                ("maxItems", Json::Int(3)),
+                            ]),
+                        ),
+                        ("minItems", Json::Int(2)),
+                        ("maxItems", Json::Int(2)),
+                    ]),
+                ),
+                (
+                    "bars",
+                    object(vec![
+                        ("type", str("array")),
+                        ("items", object(vec![("$ref", str("#/$defs/Bar"))])),
+                        ("minItems", Json::Int(2)),
+                        ("maxItems", Json::Int(2)),
+                    ]),
+                ),
+            ]),
+        ),
+        (
+            "required",
+            Json::Array(vec![
+                str("hostName"),
+                str("level"),
+                str("grid"),
+                str("bars"),
+            ]),
+        ),
+    ]);
+    assert_eq!(schema(&found[0]).unwrap(), expect);
+
+    let rendered = render(&found).unwrap();
+    assert!(rendered.starts_with(
+        "{\n  \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n  \"$defs\": {\n    \"Foo\": {\n"
+    ));
+}
+
+#[test]
+fn can_render_schema_defaults() {
+    let default = parse_quote!(3);
+    assert_eq!(
+        default_value(&default, &[2, 1]).unwrap(),
+        Json::Array(vec![
+            Json::Array(vec![Json::Int(3)]),
+            Json::Array(vec![Json::Int(3)])
+        ])
+    );
+    let default = parse_quote!([true, false]);
+    assert_eq!(
+        default_value(&default, &[2]).unwrap(),
+        Json::Array(vec![Json::Bool(true), Json::Bool(false)])
+    );
+    let default = parse_quote!(foo());
+    assert!(default_value(&default, &[]).is_err());
+}
@@ -0,0 +1,70 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use crate::header::{scan, Binding};
+use crate::typescript::render;
+use syn::parse_quote;
+
+fn bindings(file: syn::File) -> Vec<Binding> {
+    let mut found = Vec::new();
+    scan(&file.items, &mut found).unwrap();
+    found
+}
+
+#[test]
+fn can_render_typescript() {
+    let found = bindings(parse_quote! {
+        /// A thing
+        ///
+        /// With two paragraphs
+        #[binding(rename_all = "kebab-case")]
+        pub struct Foo<'a> {
+            /// The name
+            /// sbc: len = 8
+            /// sbc: default = "foo"
+            host_name: &'a str,
+            /// sbc: default = [1, 2]
+            level: [i8; 2],
+            enabled: bool,
+            grid: [[u16; 9]; 2],
+            bars: [Bar<'a>; 2],
+        }
+    });
+    let expect = r#"/* Generated by sbc-gen, do not edit */
+
+/**
+ * A thing
+ *
+ * With two paragraphs
+ */
+export interface Foo {
+  /**
+   * The name
+   * @defaultValue `"foo"`
+   */
+  "host-name": string;
+  /** @defaultValue `[1, 2]` */
+  level: [number, number];
+  enabled: boolean;
+  grid: [number[], number[]];
+  bars: [Bar, Bar];
+}
+"#;
+    assert_eq!(render(&found).unwrap(), expect);
+}
@@ -0,0 +1,106 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Render TypeScript declarations for the `#[binding]` structs of a crate.
+//! Used by the sbc-gen binary.
+//!
+//! Each struct becomes an interface of its JSON shape: keys renamed by
+//! `rename_all`, integers as `number`, nested structs by name and fixed
+//! arrays as tuples (or plain arrays when they are long). Doc comments are
+//! carried over as TSDoc, with the `sbc:` defaults as `@defaultValue`.
+
+use std::fmt::Write;
+
+use syn::parse::{Error, Result};
+
+use crate::field::{Field, FieldType};
+use crate::header::{docs, Binding};
+use crate::schema::default_value;
+
+/// Arrays longer than this are written `T[]` rather than a tuple
+const TUPLE_MAX: usize = 8;
+
+/// Render a declaration file with an interface for every binding
+pub fn render(found: &[Binding]) -> Result<String> {
+    let mut out = String::new();
+    writeln!(out, "/* Generated by sbc-gen, do not edit */").unwrap();
+    for binding in found {
+        out.push('\n');
+        write_doc(&mut out, "", &binding.docs);
+        writeln!(out, "export interface {} {{", binding.ctx.path.ident).unwrap();
+        for field in &binding.ctx.fields {
+            let mut docs = docs(&field.attributes);
+            if let Some(default) = field.attributes.seek_default() {
+                let default = default_value(default, &field.ty.dims())?;
+                docs.push(format!("@defaultValue `{}`", default.compact()));
+            }
+            write_doc(&mut out, "  ", &docs);
+            let name = field.wire_name(binding.ctx.rename_all);
+            writeln!(out, "  {}: {};", key(&name), ty(field)?).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+    Ok(out)
+}
+
+/// The TypeScript type of a field
+pub fn ty(field: &Field) -> Result<String> {
+    let mut ty = match field.ty.innermost() {
+        FieldType::Primative(p) if p == "bool" => "boolean".to_string(),
+        FieldType::Primative(_) => "number".to_string(),
+        FieldType::RefStr(_) => "string".to_string(),
+        FieldType::Struct(p) => p.ident.to_string(),
+        FieldType::Array(_) => return Err(Error::new(field.ident.span(), "unsupported type")),
+    };
+    for n in field.ty.dims().into_iter().rev() {
+        ty = match n <= TUPLE_MAX {
+            true => format!("[{}]", vec![ty; n].join(", ")),
+            false => format!("{}[]", ty),
+        };
+    }
+    Ok(ty)
+}
+
+/// A property name, quoted when it is not an identifier. IE: kebab-case
+fn key(name: &str) -> String {
+    let ident = name.chars().enumerate().all(|(i, c)| {
+        c == '_' || c == '$' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())
+    });
+    match ident && !name.is_empty() {
+        true => name.to_string(),
+        false => format!("{:?}", name),
+    }
+}
+
+fn write_doc(out: &mut String, indent: &str, lines: &[String]) {
+    match lines {
+        [] => {}
+        [line] => writeln!(out, "{}/** {} */", indent, line).unwrap(),
+        lines => {
+            writeln!(out, "{}/**", indent).unwrap();
+            for line in lines {
+                match line.is_empty() {
+                    true => writeln!(out, "{} *", indent).unwrap(),
+                    false => writeln!(out, "{} * {}", indent, line).unwrap(),
+                }
+            }
+            writeln!(out, "{} */", indent).unwrap();
+        }
+    }
+}
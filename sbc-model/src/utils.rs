@@ -0,0 +1,101 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use syn::parse::{Parse, ParseStream, Peek, Result};
+
+pub fn maybe<T: Peek, R: Parse>(token: T, input: &mut ParseStream) -> Result<Option<R>> {
+    if input.peek(token) {
+        Ok(Some(input.parse()?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// A NUL terminated "C" string literal
+pub fn c_str(s: &str, span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    let lit = syn::LitByteStr::new(format!("{}\0", s).as_bytes(), span);
+    quote::quote! {#lit.as_ptr() as *const core::ffi::c_char}
+}
+
+pub fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The number of bytes a protobuf varint takes
+pub fn varint_len(mut varint: u64) -> usize {
+    let mut len = 1;
+    while varint >= 0x80 {
+        varint >>= 7;
+        len += 1;
+    }
+    len
+}
+
+/// How a self describing binary format sizes the head before an item, given
+/// its length. Numbers take the same space in each of them
+pub struct BinaryFormat {
+    pub str_head: fn(usize) -> usize,
+    pub array_head: fn(usize) -> usize,
+    pub map_head: fn(usize) -> usize,
+}
+
+pub const CBOR: BinaryFormat = BinaryFormat {
+    str_head: cbor_head_len,
+    array_head: cbor_head_len,
+    map_head: cbor_head_len,
+};
+
+pub const MSGPACK: BinaryFormat = BinaryFormat {
+    str_head: msgpack_str_head_len,
+    array_head: msgpack_head_len,
+    map_head: msgpack_head_len,
+};
+
+/// The number of bytes the head of a CBOR item with argument n takes
+fn cbor_head_len(n: usize) -> usize {
+    match n {
+        0..=23 => 1,
+        24..=0xff => 2,
+        0x100..=0xffff => 3,
+        _ => 5,
+    }
+}
+
+/// The number of bytes the head of a MessagePack str of length n takes
+fn msgpack_str_head_len(n: usize) -> usize {
+    match n {
+        0..=31 => 1,
+        32..=0xff => 2,
+        0x100..=0xffff => 3,
+        _ => 5,
+    }
+}
+
+/// The number of bytes the head of a MessagePack array or map takes
+fn msgpack_head_len(n: usize) -> usize {
+    match n {
+        0..=15 => 1,
+        16..=0xffff => 3,
+        _ => 5,
+    }
+}
@@ -0,0 +1,689 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! CBOR encoding of the owned structs, through serde.
+//!
+//! Structs are maps keyed by their serde names, as in JSON, and arrays are
+//! arrays. Every head takes the smallest form which holds its argument, so
+//! `FOO_CBOR_MAX_LEN` bounds the encoding of the owned struct. Decoding
+//! accepts any form of a number which fits, indefinite length arrays and
+//! maps, and skips tags and unknown keys. Strings are borrowed from the
+//! input, so they must have a definite length. Nothing is allocated.
+
+use core::fmt;
+use core::str;
+
+use serde::de::{self, DeserializeSeed, Visitor};
+use serde::ser::{self, Serialize};
+
+/// How deep arrays and maps may nest in the input
+const MAX_DEPTH: usize = 32;
+
+const UINT: u8 = 0;
+const NINT: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+/// The additional information of an indefinite length, and of the break
+/// which ends it
+const INDEFINITE: u8 = 31;
+const BREAK: u8 = 0xff;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The output buffer is too small
+    BufferFull,
+    /// The input ends in the middle of a value
+    Eof,
+    /// The input is not CBOR, or does not fit the type
+    Invalid,
+    /// The type has no CBOR encoding here. IE: a sequence of unknown length
+    Unsupported,
+    /// Serde rejected the value
+    Custom,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::BufferFull => f.write_str("buffer full"),
+            Error::Eof => f.write_str("unexpected end of input"),
+            Error::Invalid => f.write_str("invalid cbor"),
+            Error::Unsupported => f.write_str("unsupported type"),
+            Error::Custom => f.write_str("serde error"),
+        }
+    }
+}
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(_msg: T) -> Error {
+        Error::Custom
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(_msg: T) -> Error {
+        Error::Custom
+    }
+}
+
+/// Encode value into buf, returning the number of bytes written
+pub fn to_slice<T: Serialize + ?Sized>(value: &T, buf: &mut [u8]) -> Result<usize> {
+    let mut ser = Serializer::new(buf);
+    value.serialize(&mut ser)?;
+    Ok(ser.len)
+}
+
+/// Decode a value from the start of bytes, returning it and the number of
+/// bytes read
+pub fn from_slice<'de, T: de::Deserialize<'de>>(bytes: &'de [u8]) -> Result<(T, usize)> {
+    let mut de = Deserializer::new(bytes);
+    let value = T::deserialize(&mut de)?;
+    Ok((value, de.index))
+}
+
+/// Decode a value from the start of bytes into dst, field by field, returning
+/// the number of bytes read. On error dst is left partly written
+pub fn from_slice_in_place<'de, T: de::Deserialize<'de>>(
+    dst: &mut T,
+    bytes: &'de [u8],
+) -> Result<usize> {
+    let mut de = Deserializer::new(bytes);
+    T::deserialize_in_place(&mut de, dst)?;
+    Ok(de.index)
+}
+
+pub struct Serializer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Serializer<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Serializer<'a> {
+        Serializer { buf, len: 0 }
+    }
+
+    /// The number of bytes written so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.buf
+            .get_mut(self.len..self.len + bytes.len())
+            .ok_or(Error::BufferFull)?
+            .copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    /// The head of an item of the major type, with its argument n
+    fn head(&mut self, major: u8, n: u64) -> Result<()> {
+        let major = major << 5;
+        match n {
+            0..=23 => self.write(&[major | n as u8]),
+            24..=0xff => self.write(&[major | 24, n as u8]),
+            0x100..=0xffff => {
+                self.write(&[major | 25])?;
+                self.write(&(n as u16).to_be_bytes())
+            }
+            0x1_0000..=0xffff_ffff => {
+                self.write(&[major | 26])?;
+                self.write(&(n as u32).to_be_bytes())
+            }
+            _ => {
+                self.write(&[major | 27])?;
+                self.write(&n.to_be_bytes())
+            }
+        }
+    }
+
+    fn int(&mut self, n: i64) -> Result<()> {
+        match n {
+            0.. => self.head(UINT, n as u64),
+            // -1 - n, without overflow
+            _ => self.head(NINT, !(n as u64)),
+        }
+    }
+}
+
+impl<'a, 'b> ser::Serializer for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.write(&[if v { 0xf5 } else { 0xf4 }])
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.int(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.int(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.int(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.int(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.head(UINT, v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.head(UINT, v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.head(UINT, v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.head(UINT, v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.write(&[0xfa])?;
+        self.write(&v.to_be_bytes())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.write(&[0xfb])?;
+        self.write(&v.to_be_bytes())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.head(TEXT, v.len() as u64)?;
+        self.write(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.head(BYTES, v.len() as u64)?;
+        self.write(v)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.write(&[0xf6])
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.write(&[0xf6])
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.head(MAP, 1)?;
+        self.serialize_str(variant)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        self.head(ARRAY, len.ok_or(Error::Unsupported)? as u64)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self> {
+        self.head(ARRAY, len as u64)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self> {
+        self.head(MAP, 1)?;
+        self.serialize_str(variant)?;
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.head(MAP, len.ok_or(Error::Unsupported)? as u64)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self> {
+        self.head(MAP, len as u64)?;
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self> {
+        self.head(MAP, 1)?;
+        self.serialize_str(variant)?;
+        self.serialize_struct(name, len)
+    }
+
+    fn collect_str<T: fmt::Display + ?Sized>(self, _value: &T) -> Result<()> {
+        Err(Error::Unsupported)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<'a, 'b> ser::SerializeSeq for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeTuple for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeTupleStruct for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeTupleVariant for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeMap for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeStruct for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::Serializer::serialize_str(&mut **self, key)?;
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeStructVariant for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::Serializer::serialize_str(&mut **self, key)?;
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub struct Deserializer<'de> {
+    slice: &'de [u8],
+    index: usize,
+    depth: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn new(slice: &'de [u8]) -> Deserializer<'de> {
+        Deserializer {
+            slice,
+            index: 0,
+            depth: 0,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'de [u8]> {
+        let end = self.index.checked_add(len).ok_or(Error::Eof)?;
+        let bytes = self.slice.get(self.index..end).ok_or(Error::Eof)?;
+        self.index = end;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// The argument which follows the initial byte of an item, or None for
+    /// an indefinite length
+    fn argument(&mut self, info: u8) -> Result<Option<u64>> {
+        match info {
+            0..=23 => Ok(Some(info as u64)),
+            24 => Ok(Some(self.u8()? as u64)),
+            25 => Ok(Some(u16::from_be_bytes(self.take_array()?) as u64)),
+            26 => Ok(Some(u32::from_be_bytes(self.take_array()?) as u64)),
+            27 => Ok(Some(u64::from_be_bytes(self.take_array()?))),
+            INDEFINITE => Ok(None),
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    fn seq<V: Visitor<'de>>(&mut self, visitor: V, left: Option<usize>) -> Result<V::Value> {
+        self.nest(|de| {
+            let mut access = Access { de, left };
+            let value = visitor.visit_seq(&mut access)?;
+            access.end().map(|_| value)
+        })
+    }
+
+    fn map<V: Visitor<'de>>(&mut self, visitor: V, left: Option<usize>) -> Result<V::Value> {
+        self.nest(|de| {
+            let mut access = Access { de, left };
+            let value = visitor.visit_map(&mut access)?;
+            access.end().map(|_| value)
+        })
+    }
+
+    fn nest<T, F: FnOnce(&mut Self) -> Result<T>>(&mut self, f: F) -> Result<T> {
+        if self.depth == MAX_DEPTH {
+            return Err(Error::Invalid);
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+}
+
+/// A length from an argument, which can not fit in the input when it does
+/// not fit in a usize
+fn to_len(n: u64) -> Result<usize> {
+    usize::try_from(n).map_err(|_| Error::Eof)
+}
+
+/// Widen a half precision float, which serde has no type for
+fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exp = (half >> 10) & 0x1f;
+    let frac = (half & 0x3ff) as u32;
+    match exp {
+        // subnormal, frac * 2^-24
+        0 => {
+            let value = frac as f32 / (1 << 24) as f32;
+            f32::from_bits(sign | value.to_bits())
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | frac << 13),
+        _ => f32::from_bits(sign | (exp as u32 + 112) << 23 | frac << 13),
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let initial = self.u8()?;
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        match (major, info) {
+            (SIMPLE, 20) => visitor.visit_bool(false),
+            (SIMPLE, 21) => visitor.visit_bool(true),
+            (SIMPLE, 22) | (SIMPLE, 23) => visitor.visit_unit(),
+            (SIMPLE, 25) => visitor.visit_f32(f16_to_f32(u16::from_be_bytes(self.take_array()?))),
+            (SIMPLE, 26) => visitor.visit_f32(f32::from_be_bytes(self.take_array()?)),
+            (SIMPLE, 27) => visitor.visit_f64(f64::from_be_bytes(self.take_array()?)),
+            // the other simple values, and a break outside of an indefinite length
+            (SIMPLE, _) => Err(Error::Invalid),
+            (major, info) => match (major, self.argument(info)?) {
+                (UINT, Some(n)) => visitor.visit_u64(n),
+                (NINT, Some(n)) => match i64::try_from(n) {
+                    // -1 - n, without overflow
+                    Ok(n) => visitor.visit_i64(!n),
+                    Err(_) => Err(Error::Invalid),
+                },
+                (BYTES, Some(n)) => visitor.visit_borrowed_bytes(self.take(to_len(n)?)?),
+                (TEXT, Some(n)) => {
+                    let bytes = self.take(to_len(n)?)?;
+                    visitor.visit_borrowed_str(str::from_utf8(bytes).map_err(|_| Error::Invalid)?)
+                }
+                (ARRAY, n) => {
+                    let left = n.map(to_len).transpose()?;
+                    self.seq(visitor, left)
+                }
+                (MAP, n) => {
+                    let left = n.map(to_len).transpose()?;
+                    self.map(visitor, left)
+                }
+                // the meaning of a tag is left to the caller, so read what it tags
+                (TAG, Some(_)) => self.nest(|de| de.deserialize_any(visitor)),
+                // indefinite length strings would need somewhere to join their chunks
+                _ => Err(Error::Invalid),
+            },
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.slice.get(self.index) {
+            Some(0xf6) | Some(0xf7) => {
+                self.index += 1;
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+/// The elements of an array, or the pairs of a map, left to read. None
+/// reads until a break
+struct Access<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    left: Option<usize>,
+}
+
+impl<'a, 'de> Access<'a, 'de> {
+    /// Whether there is another element, consuming the break after the last
+    /// one of an indefinite length
+    fn next(&mut self) -> Result<bool> {
+        match self.left {
+            Some(0) => Ok(false),
+            Some(ref mut left) => {
+                *left -= 1;
+                Ok(true)
+            }
+            None => match self.de.slice.get(self.de.index) {
+                Some(&BREAK) => {
+                    self.de.index += 1;
+                    self.left = Some(0);
+                    Ok(false)
+                }
+                Some(_) => Ok(true),
+                None => Err(Error::Eof),
+            },
+        }
+    }
+
+    /// Fails when the visitor stopped before the last element
+    fn end(&mut self) -> Result<()> {
+        match self.left {
+            Some(0) => Ok(()),
+            Some(_) => Err(Error::Invalid),
+            None => match self.next()? {
+                false => Ok(()),
+                true => Err(Error::Invalid),
+            },
+        }
+    }
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for Access<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.next()? {
+            false => Ok(None),
+            true => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.left
+    }
+}
+
+impl<'a, 'de> de::MapAccess<'de> for Access<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.next()? {
+            false => Ok(None),
+            true => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.left
+    }
+}
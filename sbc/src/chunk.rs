@@ -0,0 +1,185 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Resumable printing for callers which cannot hold a `MAX_LEN` buffer.
+//!
+//! Each `#[binding]` generates a small state machine over its fields. The
+//! machine at each nesting level keeps its position in `ChunkState::cursor`,
+//! so a call can stop when the callers buffer is full and the next call picks
+//! up at the same field (or array index). A value which was cut in half is
+//! printed again with the bytes already delivered skipped.
+
+use serde::Serialize;
+
+use crate::writer;
+
+/// Maximum nesting of structs and arrays
+pub const DEPTH: usize = 8;
+
+/// Zero initialize to start printing from the beginning
+#[repr(C)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct ChunkState {
+    /// Position of the state machine at each nesting level
+    pub cursor: [u32; DEPTH],
+    /// Bytes of the current step already delivered
+    pub skip: u32,
+    /// Set once the closing bracket has been delivered
+    pub done: bool,
+}
+
+// sbc-gen defines the state in C headers with this layout
+const _: () = assert!(core::mem::size_of::<ChunkState>() == 40);
+const _: () = assert!(core::mem::offset_of!(ChunkState, skip) == 32);
+const _: () = assert!(core::mem::offset_of!(ChunkState, done) == 36);
+
+impl ChunkState {
+    pub fn new() -> ChunkState {
+        Default::default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// The buffer is full. Resume with the next call.
+    Full,
+    /// Nesting too deep, or the value could not be serialized
+    Error,
+}
+
+/// Implemented by `#[binding]` for each owned struct
+pub trait Chunk {
+    fn chunk(&self, c: &mut Chunker, depth: usize) -> Result<(), Stop>;
+}
+
+pub struct Chunker<'a> {
+    state: &'a mut ChunkState,
+    buf: &'a mut [u8],
+    pos: usize,
+    seen: usize,
+}
+
+impl<'a> Chunker<'a> {
+    /// Position of the state machine at depth
+    pub fn cursor(&self, depth: usize) -> Result<u32, Stop> {
+        self.state.cursor.get(depth).copied().ok_or(Stop::Error)
+    }
+
+    /// Finish the step at depth and reset the nested state machines
+    pub fn next(&mut self, depth: usize) {
+        self.state.cursor[depth] += 1;
+        self.state.cursor[depth + 1..]
+            .iter_mut()
+            .for_each(|c| *c = 0);
+        self.state.skip = 0;
+        self.seen = 0;
+    }
+
+    /// Emit bytes as part of the current step
+    pub fn raw(&mut self, bytes: &[u8]) -> Result<(), Stop> {
+        let result = writer::Write::write(self, bytes);
+        self.stop(result)
+    }
+
+    /// Emit a value as part of the current step
+    pub fn leaf<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Stop> {
+        let result = writer::to_writer(value, self).map(|_| ());
+        self.stop(result)
+    }
+
+    fn stop(&mut self, result: writer::Result<()>) -> Result<(), Stop> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(writer::Error::Write) => {
+                self.state.skip = self.seen as u32;
+                Err(Stop::Full)
+            }
+            Err(_) => Err(Stop::Error),
+        }
+    }
+}
+
+impl<'a> writer::Write for Chunker<'a> {
+    fn write(&mut self, mut bytes: &[u8]) -> writer::Result<()> {
+        // Drop what the previous call already delivered
+        let skip = (self.state.skip as usize).saturating_sub(self.seen);
+        let drop = skip.min(bytes.len());
+        self.seen += drop;
+        bytes = &bytes[drop..];
+
+        let n = bytes.len().min(self.buf.len() - self.pos);
+        self.buf[self.pos..self.pos + n].copy_from_slice(&bytes[..n]);
+        self.pos += n;
+        self.seen += n;
+        if n < bytes.len() {
+            Err(writer::Error::Write)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The state machine for an array. Element i is printed by each at depth + 1.
+pub fn array<T, F>(c: &mut Chunker, depth: usize, items: &[T], mut each: F) -> Result<(), Stop>
+where
+    F: FnMut(&mut Chunker, &T, usize) -> Result<(), Stop>,
+{
+    loop {
+        let step = c.cursor(depth)? as usize;
+        match step {
+            0 => c.raw(b"[")?,
+            s if s <= items.len() * 2 => match (s - 1) % 2 {
+                0 if s > 1 => c.raw(b",")?,
+                0 => {}
+                _ => each(c, &items[(s - 1) / 2], depth + 1)?,
+            },
+            s if s == items.len() * 2 + 1 => c.raw(b"]")?,
+            _ => return Ok(()),
+        }
+        c.next(depth);
+    }
+}
+
+/// Print the next part of data into buf. Returns the number of bytes written,
+/// or 0 once everything has been printed.
+pub fn print_chunk<T: Chunk + ?Sized>(
+    data: &T,
+    state: &mut ChunkState,
+    buf: &mut [u8],
+) -> Result<usize, Stop> {
+    if state.done {
+        return Ok(0);
+    } else if buf.is_empty() {
+        return Err(Stop::Error);
+    }
+    let mut c = Chunker {
+        state,
+        buf,
+        pos: 0,
+        seen: 0,
+    };
+    match data.chunk(&mut c, 0) {
+        Ok(()) => {
+            c.state.done = true;
+            Ok(c.pos)
+        }
+        Err(Stop::Full) => Ok(c.pos),
+        Err(Stop::Error) => Err(Stop::Error),
+    }
+}
@@ -0,0 +1,207 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Static descriptions of the owned structs, so that "C" code can walk any
+//! bound struct without per-type code.
+//!
+//! The `descriptor` binding of `#[binding]` adds a `FOO_DESCRIPTOR` and its
+//! `FOO_FIELDS` for each struct. "C" links them by the symbols of the naming,
+//! IE: `sbc_descriptor_foo` and `sbc_descriptor_foo_fields`. A field of a
+//! nested struct points at the nested `BAR_DESCRIPTOR`, so a struct nested in
+//! one with a descriptor must keep its own.
+
+use core::ffi::c_char;
+
+/// The type of a field. For arrays, the type of the innermost element
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    /// A NUL terminated string in a byte array of `capacity`
+    Str,
+    /// A nested struct described by `nested`
+    Struct,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct FieldDescriptor {
+    /// NUL terminated name of the field
+    name: *const c_char,
+    /// NUL terminated key of the field in JSON
+    wire_name: *const c_char,
+    kind: Kind,
+    /// Byte offset of the field in the owned struct
+    offset: usize,
+    /// Number of elements, all dimensions of an array multiplied. 1 otherwise
+    count: usize,
+    /// The dimensions of an array, outermost first
+    dims: *const usize,
+    /// Number of dimensions. 0 when not an array
+    rank: usize,
+    /// Size in bytes of each string (including the NUL). 0 otherwise
+    capacity: usize,
+    /// The nested struct, or null
+    nested: *const Descriptor,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Descriptor {
+    /// NUL terminated name of the struct
+    name: *const c_char,
+    /// Size in bytes of the owned struct
+    size: usize,
+    fields: *const FieldDescriptor,
+    len: usize,
+}
+
+// The pointers only ever point at static data
+unsafe impl Sync for FieldDescriptor {}
+unsafe impl Sync for Descriptor {}
+
+impl FieldDescriptor {
+    /// Used by `#[binding]`, which describes each field of an owned struct.
+    ///
+    /// # Safety
+    ///
+    /// The pointers must be static: name and wire_name NUL terminated utf8,
+    /// dims rank elements long and nested null or a descriptor. The rest must
+    /// describe the field, as the table engine reads and writes through it.
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    pub const unsafe fn new(
+        name: *const c_char,
+        wire_name: *const c_char,
+        kind: Kind,
+        offset: usize,
+        count: usize,
+        dims: *const usize,
+        rank: usize,
+        capacity: usize,
+        nested: *const Descriptor,
+    ) -> FieldDescriptor {
+        FieldDescriptor {
+            name,
+            wire_name,
+            kind,
+            offset,
+            count,
+            dims,
+            rank,
+            capacity,
+            nested,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        unsafe { as_str(self.name) }
+    }
+
+    pub fn wire_name(&self) -> &'static str {
+        unsafe { as_str(self.wire_name) }
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Byte offset of the field in the owned struct
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Number of elements, all dimensions of an array multiplied. 1 otherwise
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Size in bytes of each string (including the NUL). 0 otherwise
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn nested(&self) -> Option<&'static Descriptor> {
+        unsafe { self.nested.as_ref() }
+    }
+
+    /// The dimensions of an array, outermost first. Empty when not an array
+    pub fn dims(&self) -> &'static [usize] {
+        unsafe { core::slice::from_raw_parts(self.dims, self.rank) }
+    }
+
+    /// Size in bytes of each element
+    pub fn size(&self) -> usize {
+        match self.kind {
+            Kind::Bool | Kind::U8 | Kind::I8 => 1,
+            Kind::U16 | Kind::I16 => 2,
+            Kind::U32 | Kind::I32 => 4,
+            Kind::Str => self.capacity,
+            Kind::Struct => self.nested().map(|d| d.size).unwrap_or(0),
+        }
+    }
+}
+
+impl Descriptor {
+    /// Used by `#[binding]`, which describes each owned struct.
+    ///
+    /// # Safety
+    ///
+    /// name must be a static NUL terminated utf8 string and fields a static
+    /// table len long. size must be the size of the owned struct.
+    #[doc(hidden)]
+    pub const unsafe fn new(
+        name: *const c_char,
+        size: usize,
+        fields: *const FieldDescriptor,
+        len: usize,
+    ) -> Descriptor {
+        Descriptor {
+            name,
+            size,
+            fields,
+            len,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        unsafe { as_str(self.name) }
+    }
+
+    /// Size in bytes of the owned struct
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn fields(&self) -> &'static [FieldDescriptor] {
+        unsafe { core::slice::from_raw_parts(self.fields, self.len) }
+    }
+}
+
+/// Caller must ensure ptr is a static NUL terminated string
+unsafe fn as_str(ptr: *const c_char) -> &'static str {
+    let s = unsafe { core::ffi::CStr::from_ptr(ptr) };
+    s.to_str().unwrap_or("")
+}
@@ -0,0 +1,47 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Compare two owned structs and print the difference as a JSON merge patch.
+//!
+//! The patch holds only the changed fields. Nested structs are patched
+//! field by field. Arrays are compared element by element but, as merge
+//! patches can not address array elements, a changed array is printed whole.
+
+use serde::{Serialize, Serializer};
+
+/// Implemented by `#[binding]` for each owned struct
+pub trait Diff {
+    /// Bit i is set when the i'th field (in declaration order) differs
+    fn changed(&self, other: &Self) -> u64;
+
+    /// Serialize the changed fields of new, where self is the old value
+    fn serialize_patch<S: Serializer>(&self, new: &Self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Serializes as the merge patch which turns old into new
+pub struct Patch<'a, T> {
+    pub old: &'a T,
+    pub new: &'a T,
+}
+
+impl<'a, T: Diff> Serialize for Patch<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.old.serialize_patch(self.new, serializer)
+    }
+}
@@ -0,0 +1,572 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! A push parser which fills an owned struct as the JSON arrives.
+//!
+//! Bytes can be fed in fragments of any size. Strings are copied straight into
+//! the destination and only keys and numbers are buffered, so memory use does
+//! not depend on the size of the document.
+//!
+//! Each `#[binding]` implements `Feed` for its owned struct. The parser tracks
+//! where it is as a path of field (or array) indexes, and asks the struct for
+//! the `Slot` at that path when it has something to store. Fields which are
+//! missing from the document keep their current value, so initialize the
+//! destination first.
+
+/// Maximum nesting of structs and arrays
+pub const DEPTH: usize = 8;
+
+/// Longest key (or number) we buffer. Longer keys can not be ours.
+pub const KEY_LEN: usize = 32;
+
+/// What lives at a path in the destination
+pub enum Slot<'a> {
+    Object,
+    Array(usize),
+    Bool(&'a mut bool),
+    U8(&'a mut u8),
+    I8(&'a mut i8),
+    U16(&'a mut u16),
+    I16(&'a mut i16),
+    U32(&'a mut u32),
+    I32(&'a mut i32),
+    Str(&'a mut [u8]),
+    /// Nothing here, the value is ignored
+    Skip,
+}
+
+macro_rules! impl_slot_from {
+    ($t:ty, $v:ident) => {
+        impl<'a> From<&'a mut $t> for Slot<'a> {
+            fn from(v: &'a mut $t) -> Slot<'a> {
+                Slot::$v(v)
+            }
+        }
+    };
+}
+impl_slot_from!(bool, Bool);
+impl_slot_from!(u8, U8);
+impl_slot_from!(i8, I8);
+impl_slot_from!(u16, U16);
+impl_slot_from!(i16, I16);
+impl_slot_from!(u32, U32);
+impl_slot_from!(i32, I32);
+
+/// Implemented by `#[binding]` for each owned struct
+pub trait Feed {
+    /// The index of the child named token, of the value at path. Fields are
+    /// named by their wire name and array elements by their index.
+    fn step(&self, path: &[u16], token: &[u8]) -> Option<u16>;
+
+    /// The slot at path, relative to self. An empty path is self.
+    fn slot(&mut self, path: &[u16]) -> Slot<'_>;
+}
+
+/// The array index token, if it is one and it is less than len
+pub fn index(token: &[u8], len: usize) -> Option<u16> {
+    // RFC 6901 forbids leading zeros
+    if token.is_empty() || (token.len() > 1 && token[0] == b'0') {
+        return None;
+    }
+    let n = core::str::from_utf8(token).ok()?.parse::<u16>().ok()?;
+    if (n as usize) < len {
+        Some(n)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Feed more bytes
+    NeedMore,
+    /// The document ended after this many bytes of the last fragment
+    Complete(usize),
+    /// The document is invalid, or does not fit the destination
+    Error,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Lex {
+    Start = 0,
+    Value,
+    ArrayFirst,
+    ObjectFirst,
+    Key,
+    Colon,
+    After,
+    Str,
+    Escape,
+    Unicode,
+    Number,
+    Literal,
+    Skip,
+    SkipStr,
+    SkipEscape,
+    Done,
+    Error,
+}
+
+// flags
+const SKIP_VALUE: u8 = 1 << 0; // the next value is not ours
+const DISCARD: u8 = 1 << 1; // the current scalar is not ours
+const KEY: u8 = 1 << 2; // the current string is a key
+const OVERFLOW: u8 = 1 << 3; // the key did not fit in buf
+
+const LITERALS: [&[u8]; 3] = [b"true", b"false", b"null"];
+
+/// Zero initialize (or `FeedState::new()`) before the first fragment
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct FeedState {
+    path: [u16; DEPTH],
+    lens: [u16; DEPTH],
+    buf: [u8; KEY_LEN],
+    str_pos: u16,
+    unicode: u16,
+    depth: u8,
+    arrays: u8,
+    lex: Lex,
+    buf_len: u8,
+    flags: u8,
+    skip: u8,
+    literal: u8,
+    count: u8,
+    base: u8,
+}
+
+// sbc-gen defines the state in C headers as `uint16_t opaque[39]`
+const _: () = assert!(core::mem::size_of::<FeedState>() == 78);
+const _: () = assert!(core::mem::align_of::<FeedState>() == 2);
+
+impl Default for FeedState {
+    fn default() -> FeedState {
+        FeedState::new()
+    }
+}
+
+impl FeedState {
+    pub const fn new() -> FeedState {
+        FeedState {
+            path: [0; DEPTH],
+            lens: [0; DEPTH],
+            buf: [0; KEY_LEN],
+            str_pos: 0,
+            unicode: 0,
+            depth: 0,
+            arrays: 0,
+            lex: Lex::Start,
+            buf_len: 0,
+            flags: 0,
+            skip: 0,
+            literal: 0,
+            count: 0,
+            base: 0,
+        }
+    }
+
+    /// Parse a single value into the slot at path, rather than a document
+    pub fn at(dst: &mut dyn Feed, path: &[u16]) -> Option<FeedState> {
+        if path.len() > DEPTH {
+            return None;
+        }
+        let mut state = FeedState::new();
+        for (d, idx) in path.iter().enumerate() {
+            match dst.slot(&path[..d]) {
+                Slot::Object => state.open(false, 0).ok()?,
+                Slot::Array(n) => state.open(true, n).ok()?,
+                _ => return None,
+            }
+            state.path[d] = *idx;
+        }
+        state.base = state.depth;
+        state.lex = Lex::Value;
+        Some(state)
+    }
+
+    /// There are no more bytes. Completes a number at the very end
+    pub fn finish(&mut self, dst: &mut dyn Feed) -> Status {
+        if self.lex == Lex::Number {
+            match self.number(dst) {
+                Ok(()) => self.end_value(),
+                Err(()) => self.lex = Lex::Error,
+            }
+        }
+        match self.lex {
+            Lex::Done => Status::Complete(0),
+            _ => Status::Error,
+        }
+    }
+
+    /// Consume the next fragment of the document
+    pub fn feed(&mut self, dst: &mut dyn Feed, bytes: &[u8]) -> Status {
+        let mut i = 0;
+        while i < bytes.len() {
+            match self.step(dst, &bytes[i..]) {
+                Ok(n) => i += n,
+                Err(()) => {
+                    self.lex = Lex::Error;
+                    return Status::Error;
+                }
+            }
+            if self.lex == Lex::Done {
+                return Status::Complete(i);
+            }
+        }
+        match self.lex {
+            Lex::Done | Lex::Error => Status::Error,
+            _ => Status::NeedMore,
+        }
+    }
+
+    /// Process the start of bytes, returning how many bytes were consumed
+    fn step(&mut self, dst: &mut dyn Feed, bytes: &[u8]) -> Result<usize, ()> {
+        let b = bytes[0];
+        let ws = matches!(b, b' ' | b'\t' | b'\n' | b'\r');
+        match self.lex {
+            Lex::Start if ws => {}
+            Lex::Start if b == b'{' => self.begin(dst, b)?,
+            Lex::Value if ws => {}
+            Lex::Value => self.begin(dst, b)?,
+            Lex::ArrayFirst if ws => {}
+            Lex::ArrayFirst if b == b']' => self.close(b'[', 0)?,
+            Lex::ArrayFirst => {
+                self.lex = Lex::Value;
+                self.check_index()?;
+                return Ok(0);
+            }
+            Lex::ObjectFirst | Lex::Key if ws => {}
+            Lex::ObjectFirst if b == b'}' => self.close(b'{', 0)?,
+            Lex::ObjectFirst | Lex::Key if b == b'"' => {
+                self.flags = KEY;
+                self.buf_len = 0;
+                self.lex = Lex::Str;
+            }
+            Lex::Colon if ws => {}
+            Lex::Colon if b == b':' => self.lex = Lex::Value,
+            Lex::After if ws => {}
+            Lex::After => self.after(b)?,
+            Lex::Str => return self.string(dst, bytes),
+            Lex::Escape => {
+                let c = match b {
+                    b'"' | b'\\' | b'/' => b,
+                    b'b' => 0x08,
+                    b'f' => 0x0C,
+                    b'n' => b'\n',
+                    b'r' => b'\r',
+                    b't' => b'\t',
+                    b'u' => {
+                        self.unicode = 0;
+                        self.count = 0;
+                        self.lex = Lex::Unicode;
+                        return Ok(1);
+                    }
+                    _ => return Err(()),
+                };
+                self.emit(dst, &[c]);
+                self.lex = Lex::Str;
+            }
+            Lex::Unicode => {
+                let digit = (b as char).to_digit(16).ok_or(())?;
+                self.unicode = (self.unicode << 4) | digit as u16;
+                self.count += 1;
+                if self.count == 4 {
+                    // Surrogate pairs are not supported
+                    let c = char::from_u32(self.unicode as u32).unwrap_or('\u{FFFD}');
+                    let mut utf8 = [0; 4];
+                    self.emit(dst, c.encode_utf8(&mut utf8).as_bytes());
+                    self.lex = Lex::Str;
+                }
+            }
+            Lex::Number if matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') => {
+                self.push_buf(b)?;
+            }
+            Lex::Number => {
+                self.number(dst)?;
+                self.end_value();
+                return Ok(0);
+            }
+            Lex::Literal => {
+                let literal = LITERALS[self.literal as usize];
+                if literal[self.count as usize] != b {
+                    return Err(());
+                }
+                self.count += 1;
+                if self.count as usize == literal.len() {
+                    self.literal(dst)?;
+                    self.end_value();
+                }
+            }
+            Lex::Skip => match b {
+                b'"' => self.lex = Lex::SkipStr,
+                b'{' | b'[' => self.skip = self.skip.checked_add(1).ok_or(())?,
+                b'}' | b']' => {
+                    self.skip -= 1;
+                    if self.skip == 0 {
+                        self.end_value();
+                    }
+                }
+                _ => {}
+            },
+            Lex::SkipStr if b == b'\\' => self.lex = Lex::SkipEscape,
+            Lex::SkipStr if b == b'"' => self.lex = Lex::Skip,
+            Lex::SkipStr => {}
+            Lex::SkipEscape => self.lex = Lex::SkipStr,
+            _ => return Err(()),
+        }
+        Ok(1)
+    }
+
+    /// The slot the current value goes into
+    fn slot<'a>(&self, dst: &'a mut dyn Feed) -> Slot<'a> {
+        dst.slot(&self.path[..self.depth as usize])
+    }
+
+    /// A value ended. Are we back where we started?
+    fn end_value(&mut self) {
+        self.lex = if self.depth == self.base {
+            Lex::Done
+        } else {
+            Lex::After
+        };
+    }
+
+    fn is_array(&self) -> bool {
+        self.depth > 0 && self.arrays & (1 << (self.depth - 1)) != 0
+    }
+
+    fn check_index(&self) -> Result<(), ()> {
+        let d = self.depth as usize - 1;
+        if self.path[d] < self.lens[d] {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// The first byte of a value
+    fn begin(&mut self, dst: &mut dyn Feed, b: u8) -> Result<(), ()> {
+        let discard = self.flags & SKIP_VALUE != 0;
+        self.flags = if discard { DISCARD } else { 0 };
+        let slot = if discard { Slot::Skip } else { self.slot(dst) };
+        match (b, slot) {
+            (b'{', Slot::Skip) | (b'[', Slot::Skip) => {
+                self.skip = 1;
+                self.lex = Lex::Skip;
+            }
+            (b'{', Slot::Object) => {
+                self.open(false, 0)?;
+                self.lex = Lex::ObjectFirst;
+            }
+            (b'[', Slot::Array(n)) => {
+                self.open(true, n)?;
+                self.lex = Lex::ArrayFirst;
+            }
+            (b'"', Slot::Skip) => {
+                self.flags = DISCARD;
+                self.lex = Lex::Str;
+            }
+            (b'"', Slot::Str(_)) => {
+                self.str_pos = 0;
+                self.lex = Lex::Str;
+            }
+            (b'-' | b'0'..=b'9', slot) => {
+                match slot {
+                    Slot::Skip => self.flags = DISCARD,
+                    Slot::U8(_) | Slot::I8(_) | Slot::U16(_) | Slot::I16(_) => {}
+                    Slot::U32(_) | Slot::I32(_) => {}
+                    _ => return Err(()),
+                }
+                self.buf_len = 0;
+                self.push_buf(b)?;
+                self.lex = Lex::Number;
+            }
+            (b't' | b'f' | b'n', slot) => {
+                match (b, slot) {
+                    (_, Slot::Skip) => self.flags = DISCARD,
+                    (b't' | b'f', Slot::Bool(_)) => {}
+                    _ => return Err(()),
+                }
+                self.literal = match b {
+                    b't' => 0,
+                    b'f' => 1,
+                    _ => 2,
+                };
+                self.count = 1;
+                self.lex = Lex::Literal;
+            }
+            _ => return Err(()),
+        }
+        Ok(())
+    }
+
+    fn open(&mut self, array: bool, len: usize) -> Result<(), ()> {
+        let d = self.depth as usize;
+        if d == DEPTH {
+            return Err(());
+        }
+        self.path[d] = 0;
+        self.lens[d] = len.min(u16::MAX as usize) as u16;
+        if array {
+            self.arrays |= 1 << d;
+        } else {
+            self.arrays &= !(1 << d);
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Close the container opened with open, which held count items
+    fn close(&mut self, open: u8, count: usize) -> Result<(), ()> {
+        if (open == b'[') != self.is_array() {
+            return Err(());
+        }
+        if open == b'[' && count != self.lens[self.depth as usize - 1] as usize {
+            return Err(());
+        }
+        self.depth -= 1;
+        self.end_value();
+        Ok(())
+    }
+
+    /// Between values of a container
+    fn after(&mut self, b: u8) -> Result<(), ()> {
+        let d = self.depth as usize - 1;
+        match (b, self.is_array()) {
+            (b',', true) => {
+                self.path[d] += 1;
+                self.check_index()?;
+                self.lex = Lex::Value;
+            }
+            (b',', false) => self.lex = Lex::Key,
+            (b']', true) => self.close(b'[', self.path[d] as usize + 1)?,
+            (b'}', false) => self.close(b'{', 0)?,
+            _ => return Err(()),
+        }
+        Ok(())
+    }
+
+    /// Inside a string. Consume as much as we can in one go
+    fn string(&mut self, dst: &mut dyn Feed, bytes: &[u8]) -> Result<usize, ()> {
+        let end = bytes
+            .iter()
+            .position(|b| *b == b'"' || *b == b'\\')
+            .unwrap_or(bytes.len());
+        self.emit(dst, &bytes[..end]);
+        match bytes.get(end) {
+            Some(b'\\') => self.lex = Lex::Escape,
+            Some(_) if self.flags & KEY != 0 => {
+                let len = self.buf_len as usize;
+                let d = self.depth as usize - 1;
+                let key = match self.flags & OVERFLOW {
+                    0 => dst.step(&self.path[..d], &self.buf[..len]),
+                    _ => None,
+                };
+                match key {
+                    Some(idx) => {
+                        self.path[d] = idx;
+                        self.flags = 0;
+                    }
+                    None => self.flags = SKIP_VALUE,
+                }
+                self.lex = Lex::Colon;
+            }
+            Some(_) => {
+                if self.flags & DISCARD == 0 {
+                    if let Slot::Str(s) = self.slot(dst) {
+                        // NUL terminate and zero the rest like SafeCopy
+                        if !s.is_empty() {
+                            let pos = (self.str_pos as usize).min(s.len() - 1);
+                            s[pos..].iter_mut().for_each(|b| *b = 0);
+                        }
+                    }
+                }
+                self.end_value();
+            }
+            None => return Ok(end),
+        }
+        Ok(end + 1)
+    }
+
+    /// Decoded string bytes
+    fn emit(&mut self, dst: &mut dyn Feed, bytes: &[u8]) {
+        if bytes.is_empty() {
+        } else if self.flags & KEY != 0 {
+            let len = self.buf_len as usize;
+            if len + bytes.len() <= KEY_LEN {
+                self.buf[len..len + bytes.len()].copy_from_slice(bytes);
+                self.buf_len += bytes.len() as u8;
+            } else {
+                self.flags |= OVERFLOW;
+            }
+        } else if self.flags & DISCARD == 0 {
+            if let Slot::Str(s) = self.slot(dst) {
+                // Leave room for the NUL
+                let pos = self.str_pos as usize;
+                let room = s.len().saturating_sub(1).saturating_sub(pos);
+                let n = room.min(bytes.len());
+                s[pos..pos + n].copy_from_slice(&bytes[..n]);
+                self.str_pos += n as u16;
+            }
+        }
+    }
+
+    fn push_buf(&mut self, b: u8) -> Result<(), ()> {
+        let len = self.buf_len as usize;
+        if len < KEY_LEN {
+            self.buf[len] = b;
+            self.buf_len += 1;
+            Ok(())
+        } else if self.flags & DISCARD != 0 {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    fn number(&mut self, dst: &mut dyn Feed) -> Result<(), ()> {
+        if self.flags & DISCARD != 0 {
+            return Ok(());
+        }
+        let text = core::str::from_utf8(&self.buf[..self.buf_len as usize]).map_err(|_| ())?;
+        let n: i64 = text.parse().map_err(|_| ())?;
+        match self.slot(dst) {
+            Slot::U8(v) => *v = n.try_into().map_err(|_| ())?,
+            Slot::I8(v) => *v = n.try_into().map_err(|_| ())?,
+            Slot::U16(v) => *v = n.try_into().map_err(|_| ())?,
+            Slot::I16(v) => *v = n.try_into().map_err(|_| ())?,
+            Slot::U32(v) => *v = n.try_into().map_err(|_| ())?,
+            Slot::I32(v) => *v = n.try_into().map_err(|_| ())?,
+            _ => return Err(()),
+        }
+        Ok(())
+    }
+
+    fn literal(&mut self, dst: &mut dyn Feed) -> Result<(), ()> {
+        if self.flags & DISCARD != 0 {
+            return Ok(());
+        }
+        match self.slot(dst) {
+            Slot::Bool(v) => *v = self.literal == 0,
+            _ => return Err(()),
+        }
+        Ok(())
+    }
+}
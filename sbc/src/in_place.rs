@@ -0,0 +1,54 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Write into a struct where it lives instead of building it on the stack.
+//!
+//! `*dst = Default::default()` and friends construct the whole value first
+//! and then move it, so stack use grows with the size of the struct. The
+//! traits below are implemented field by field by `#[binding]` instead.
+//!
+//! The cbor_parse and msgpack_parse bindings decode into dst field by field
+//! too, through serde's `deserialize_in_place`, so a failed parse leaves dst
+//! partly written. The JSON parse binding does not: serde_json_core keeps its
+//! deserializer to itself, so the parse binding builds its value on the stack
+//! and moves it into dst. Use the feed parser (the parse_feed binding) when
+//! the struct is larger than the stack.
+
+/// Implemented by `#[binding]` for each owned struct
+pub trait Init {
+    /// Write the default value of every field
+    fn init(&mut self);
+}
+
+/// Implemented by `#[binding]` for each owned struct, from its borrowed struct
+pub trait CopyFrom<T: ?Sized> {
+    /// Copy every field of src
+    fn copy_from(&mut self, src: &T);
+}
+
+/// Copy a string into an owned string field, truncating so the last byte is
+/// always NUL and zero filling the rest
+pub fn copy_str(dst: &mut [u8], src: &str) {
+    let len = src.len().min(dst.len());
+    dst[..len].copy_from_slice(&src.as_bytes()[..len]);
+    dst[len..].fill(0);
+    if let Some(last) = dst.last_mut() {
+        *last = 0;
+    }
+}
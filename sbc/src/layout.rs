@@ -0,0 +1,125 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! The C layout of the owned structs, checked when the crate compiles.
+//!
+//! `#[binding]` describes each field of an owned struct by the size and
+//! alignment C gives it, then asserts that rustc placed every field at the
+//! offset [`Layout::new`] computes. The sbc-gen header repeats the same
+//! numbers as `_Static_assert`s, so a struct which drifts from its C
+//! declaration fails to build on either side.
+//!
+//! [`AbiHash`] folds the same description into a number which survives the
+//! build, so a loader or a persisted blob can be checked against the library
+//! it meets at run time.
+
+/// The size, alignment and field offsets of a `#[repr(C)]` struct
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layout<const N: usize> {
+    pub size: usize,
+    pub align: usize,
+    pub offsets: [usize; N],
+}
+
+impl<const N: usize> Layout<N> {
+    /// Lay out fields given as (size, align) pairs in declaration order. Each
+    /// field starts at the next multiple of its alignment and the size is
+    /// padded to a multiple of the largest alignment.
+    pub const fn new(fields: [(usize, usize); N]) -> Layout<N> {
+        let mut offsets = [0; N];
+        let mut offset = 0;
+        let mut align = 1;
+        let mut i = 0;
+        while i < N {
+            let (size, field_align) = fields[i];
+            offset = align_up(offset, field_align);
+            offsets[i] = offset;
+            offset += size;
+            if field_align > align {
+                align = field_align;
+            }
+            i += 1;
+        }
+        Layout {
+            size: align_up(offset, align),
+            align,
+            offsets,
+        }
+    }
+}
+
+const fn align_up(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+/// A 64 bit FNV-1a hash of the layout of an owned struct, built at compile
+/// time. `#[binding]` feeds it each field's name and C type in order (the
+/// hash of a nested struct standing in for its type), then the size and
+/// offsets of the struct. sbc-gen computes the same value for the header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbiHash(u64);
+
+impl AbiHash {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pub const fn new() -> AbiHash {
+        AbiHash(AbiHash::OFFSET)
+    }
+
+    pub const fn bytes(self, bytes: &[u8]) -> AbiHash {
+        let mut hash = self.0;
+        let mut i = 0;
+        while i < bytes.len() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(AbiHash::PRIME);
+            i += 1;
+        }
+        AbiHash(hash)
+    }
+
+    pub const fn str(self, s: &str) -> AbiHash {
+        self.bytes(s.as_bytes())
+    }
+
+    /// Fold in a number as 8 little endian bytes, the same on every target
+    pub const fn u64(self, n: u64) -> AbiHash {
+        self.bytes(&n.to_le_bytes())
+    }
+
+    pub const fn layout<const N: usize>(self, layout: &Layout<N>) -> AbiHash {
+        let mut hash = self.u64(layout.size as u64).u64(layout.align as u64);
+        let mut i = 0;
+        while i < N {
+            hash = hash.u64(layout.offsets[i] as u64);
+            i += 1;
+        }
+        hash
+    }
+
+    pub const fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for AbiHash {
+    fn default() -> AbiHash {
+        AbiHash::new()
+    }
+}
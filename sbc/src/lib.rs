@@ -21,6 +21,25 @@
 #![feature(lang_items)]
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "cbor")]
+pub mod cbor;
+pub mod chunk;
+pub mod descriptor;
+pub mod diff;
+pub mod feed;
+pub mod in_place;
+pub mod layout;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+pub mod owned_str;
+pub mod pointer;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+pub mod table;
+pub mod writer;
+
+use core::str::Utf8Error;
+
 pub use serde;
 pub use serde_bindgen_core_derive::binding;
 pub use serde_json_core;
@@ -67,6 +86,92 @@ impl<const N: usize> SafeCopy<u8, N> for &str {
     }
 }
 
+/// Implemented by `#[binding]` for each owned struct so that rust callers can
+/// be generic over bound types.
+pub trait Binding: Sized {
+    /// The "C" compatible version of the struct (no references)
+    type Owned: serde::Serialize + serde::de::DeserializeOwned;
+
+    /// The original struct, borrowing its strings from the parsed bytes
+    type Borrowed<'a>: serde::Serialize + serde::Deserialize<'a>;
+
+    /// Worst case length of the printed JSON
+    const MAX_LEN: usize;
+
+    fn to_owned(data: &Self::Borrowed<'_>) -> Self::Owned;
+
+    fn parse(bytes: &[u8]) -> serde_json_core::de::Result<(Self::Borrowed<'_>, usize)> {
+        serde_json_core::from_slice(bytes)
+    }
+
+    fn print(data: &Self::Borrowed<'_>, bytes: &mut [u8]) -> serde_json_core::ser::Result<usize> {
+        serde_json_core::to_slice(data, bytes)
+    }
+}
+
+/// A string slice laid out as `struct { const char* ptr; size_t len; }` so that
+/// "C" callers can hand us strings they already own without copying them.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct StrView {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+impl StrView {
+    /// Read the view as a str. A null pointer reads as an empty string, and
+    /// bytes which are not utf8 are an error.
+    ///
+    /// # Safety
+    ///
+    /// Unless ptr is null it must point at len initialized bytes, which are
+    /// not written to while the returned str is alive.
+    pub unsafe fn as_str(&self) -> Result<&str, Utf8Error> {
+        if self.ptr.is_null() {
+            Ok("")
+        } else {
+            core::str::from_utf8(core::slice::from_raw_parts(self.ptr, self.len))
+        }
+    }
+}
+
+impl From<&str> for StrView {
+    fn from(s: &str) -> StrView {
+        StrView {
+            ptr: s.as_ptr(),
+            len: s.len(),
+        }
+    }
+}
+
+/// Implemented by `#[binding]` for each borrowed struct, from its view
+pub trait FromView<'a, V: ?Sized>: Sized {
+    /// Borrow every string of the view.
+    ///
+    /// # Safety
+    ///
+    /// Every `StrView` in view must be valid as described by
+    /// [`StrView::as_str`] for 'a.
+    unsafe fn from_view(view: &'a V) -> Result<Self, Utf8Error>;
+}
+
+impl<'a> FromView<'a, StrView> for &'a str {
+    unsafe fn from_view(view: &'a StrView) -> Result<&'a str, Utf8Error> {
+        view.as_str()
+    }
+}
+
+impl<'a, V, T: FromView<'a, V>, const N: usize> FromView<'a, [V; N]> for [T; N] {
+    unsafe fn from_view(view: &'a [V; N]) -> Result<[T; N], Utf8Error> {
+        let mut result = Ok(());
+        let items: [Option<T>; N] = core::array::from_fn(|i| match result {
+            Ok(()) => T::from_view(&view[i]).map_err(|e| result = Err(e)).ok(),
+            Err(_) => None,
+        });
+        result.map(|()| items.map(|item| item.unwrap()))
+    }
+}
+
 #[cfg(not(feature = "testing"))]
 #[cfg(not(feature = "full"))]
 #[lang = "eh_personality"]
@@ -0,0 +1,687 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! MessagePack encoding of the owned structs, through serde.
+//!
+//! Structs are maps keyed by their serde names, as in JSON, and arrays are
+//! arrays. Numbers and lengths take the smallest form which holds them, so
+//! `FOO_MSGPACK_MAX_LEN` bounds the encoding of the owned struct. Decoding
+//! accepts any form of a number which fits, structs as arrays of their
+//! fields, and skips unknown keys. Nothing is allocated, and strings are
+//! borrowed from the input.
+
+use core::fmt;
+use core::str;
+
+use serde::de::{self, DeserializeSeed, Visitor};
+use serde::ser::{self, Serialize};
+
+/// How deep arrays and maps may nest in the input
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The output buffer is too small
+    BufferFull,
+    /// The input ends in the middle of a value
+    Eof,
+    /// The input is not MessagePack, or does not fit the type
+    Invalid,
+    /// The type has no MessagePack encoding here. IE: a sequence of unknown length
+    Unsupported,
+    /// Serde rejected the value
+    Custom,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::BufferFull => f.write_str("buffer full"),
+            Error::Eof => f.write_str("unexpected end of input"),
+            Error::Invalid => f.write_str("invalid messagepack"),
+            Error::Unsupported => f.write_str("unsupported type"),
+            Error::Custom => f.write_str("serde error"),
+        }
+    }
+}
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(_msg: T) -> Error {
+        Error::Custom
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(_msg: T) -> Error {
+        Error::Custom
+    }
+}
+
+/// Encode value into buf, returning the number of bytes written
+pub fn to_slice<T: Serialize + ?Sized>(value: &T, buf: &mut [u8]) -> Result<usize> {
+    let mut ser = Serializer::new(buf);
+    value.serialize(&mut ser)?;
+    Ok(ser.len)
+}
+
+/// Decode a value from the start of bytes, returning it and the number of
+/// bytes read
+pub fn from_slice<'de, T: de::Deserialize<'de>>(bytes: &'de [u8]) -> Result<(T, usize)> {
+    let mut de = Deserializer::new(bytes);
+    let value = T::deserialize(&mut de)?;
+    Ok((value, de.index))
+}
+
+/// Decode a value from the start of bytes into dst, field by field, returning
+/// the number of bytes read. On error dst is left partly written
+pub fn from_slice_in_place<'de, T: de::Deserialize<'de>>(
+    dst: &mut T,
+    bytes: &'de [u8],
+) -> Result<usize> {
+    let mut de = Deserializer::new(bytes);
+    T::deserialize_in_place(&mut de, dst)?;
+    Ok(de.index)
+}
+
+pub struct Serializer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Serializer<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Serializer<'a> {
+        Serializer { buf, len: 0 }
+    }
+
+    /// The number of bytes written so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.buf
+            .get_mut(self.len..self.len + bytes.len())
+            .ok_or(Error::BufferFull)?
+            .copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    fn uint(&mut self, n: u64) -> Result<()> {
+        match n {
+            0..=0x7f => self.write(&[n as u8]),
+            0x80..=0xff => self.write(&[0xcc, n as u8]),
+            0x100..=0xffff => self.write_u16(0xcd, n as u16),
+            0x1_0000..=0xffff_ffff => self.write_u32(0xce, n as u32),
+            _ => {
+                self.write(&[0xcf])?;
+                self.write(&n.to_be_bytes())
+            }
+        }
+    }
+
+    fn int(&mut self, n: i64) -> Result<()> {
+        match n {
+            0.. => self.uint(n as u64),
+            -32..=-1 => self.write(&[n as u8]),
+            -0x80..=-33 => self.write(&[0xd0, n as u8]),
+            -0x8000..=-0x81 => self.write_u16(0xd1, n as u16),
+            -0x8000_0000..=-0x8001 => self.write_u32(0xd2, n as u32),
+            _ => {
+                self.write(&[0xd3])?;
+                self.write(&n.to_be_bytes())
+            }
+        }
+    }
+
+    fn write_u16(&mut self, marker: u8, n: u16) -> Result<()> {
+        self.write(&[marker])?;
+        self.write(&n.to_be_bytes())
+    }
+
+    fn write_u32(&mut self, marker: u8, n: u32) -> Result<()> {
+        self.write(&[marker])?;
+        self.write(&n.to_be_bytes())
+    }
+
+    /// The head of a str, bin, array or map. The fix form, when there is
+    /// one, holds lengths below fix_max
+    fn head(&mut self, fix: Option<(u8, usize)>, markers: [u8; 3], n: usize) -> Result<()> {
+        match fix {
+            Some((fix, fix_max)) if n < fix_max => self.write(&[fix | n as u8]),
+            _ if n <= 0xff && markers[0] != 0 => self.write(&[markers[0], n as u8]),
+            _ if n <= 0xffff => self.write_u16(markers[1], n as u16),
+            _ => self.write_u32(
+                markers[2],
+                u32::try_from(n).map_err(|_| Error::Unsupported)?,
+            ),
+        }
+    }
+
+    fn str_head(&mut self, n: usize) -> Result<()> {
+        self.head(Some((0xa0, 32)), [0xd9, 0xda, 0xdb], n)
+    }
+
+    fn array_head(&mut self, n: usize) -> Result<()> {
+        self.head(Some((0x90, 16)), [0, 0xdc, 0xdd], n)
+    }
+
+    fn map_head(&mut self, n: usize) -> Result<()> {
+        self.head(Some((0x80, 16)), [0, 0xde, 0xdf], n)
+    }
+}
+
+impl<'a, 'b> ser::Serializer for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.write(&[if v { 0xc3 } else { 0xc2 }])
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.int(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.int(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.int(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.int(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.uint(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.uint(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.uint(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.uint(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.write(&[0xca])?;
+        self.write(&v.to_be_bytes())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.write(&[0xcb])?;
+        self.write(&v.to_be_bytes())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.str_head(v.len())?;
+        self.write(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.head(None, [0xc4, 0xc5, 0xc6], v.len())?;
+        self.write(v)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.write(&[0xc0])
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.write(&[0xc0])
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.map_head(1)?;
+        self.serialize_str(variant)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        self.array_head(len.ok_or(Error::Unsupported)?)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self> {
+        self.array_head(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self> {
+        self.map_head(1)?;
+        self.serialize_str(variant)?;
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.map_head(len.ok_or(Error::Unsupported)?)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self> {
+        self.map_head(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self> {
+        self.map_head(1)?;
+        self.serialize_str(variant)?;
+        self.serialize_struct(name, len)
+    }
+
+    fn collect_str<T: fmt::Display + ?Sized>(self, _value: &T) -> Result<()> {
+        Err(Error::Unsupported)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<'a, 'b> ser::SerializeSeq for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeTuple for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeTupleStruct for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeTupleVariant for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeMap for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeStruct for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::Serializer::serialize_str(&mut **self, key)?;
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeStructVariant for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::Serializer::serialize_str(&mut **self, key)?;
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub struct Deserializer<'de> {
+    slice: &'de [u8],
+    index: usize,
+    depth: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn new(slice: &'de [u8]) -> Deserializer<'de> {
+        Deserializer {
+            slice,
+            index: 0,
+            depth: 0,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'de [u8]> {
+        let end = self.index.checked_add(len).ok_or(Error::Eof)?;
+        let bytes = self.slice.get(self.index..end).ok_or(Error::Eof)?;
+        self.index = end;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes(self.take_array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take_array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take_array()?))
+    }
+
+    fn str<V: Visitor<'de>>(&mut self, visitor: V, len: usize) -> Result<V::Value> {
+        let bytes = self.take(len)?;
+        visitor.visit_borrowed_str(str::from_utf8(bytes).map_err(|_| Error::Invalid)?)
+    }
+
+    fn seq<V: Visitor<'de>>(&mut self, visitor: V, len: usize) -> Result<V::Value> {
+        self.nest(|de| {
+            let mut access = Access { de, left: len };
+            let value = visitor.visit_seq(&mut access)?;
+            match access.left {
+                0 => Ok(value),
+                _ => Err(Error::Invalid),
+            }
+        })
+    }
+
+    fn map<V: Visitor<'de>>(&mut self, visitor: V, len: usize) -> Result<V::Value> {
+        self.nest(|de| {
+            let mut access = Access { de, left: len };
+            let value = visitor.visit_map(&mut access)?;
+            match access.left {
+                0 => Ok(value),
+                _ => Err(Error::Invalid),
+            }
+        })
+    }
+
+    fn nest<T, F: FnOnce(&mut Self) -> Result<T>>(&mut self, f: F) -> Result<T> {
+        if self.depth == MAX_DEPTH {
+            return Err(Error::Invalid);
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.u8()? {
+            m @ 0x00..=0x7f => visitor.visit_u64(m as u64),
+            m @ 0x80..=0x8f => self.map(visitor, (m & 0x0f) as usize),
+            m @ 0x90..=0x9f => self.seq(visitor, (m & 0x0f) as usize),
+            m @ 0xa0..=0xbf => self.str(visitor, (m & 0x1f) as usize),
+            0xc0 => visitor.visit_unit(),
+            0xc2 => visitor.visit_bool(false),
+            0xc3 => visitor.visit_bool(true),
+            m @ 0xc4..=0xc6 => {
+                let len = match m {
+                    0xc4 => self.u8()? as usize,
+                    0xc5 => self.u16()? as usize,
+                    _ => self.u32()? as usize,
+                };
+                visitor.visit_borrowed_bytes(self.take(len)?)
+            }
+            0xca => visitor.visit_f32(f32::from_bits(self.u32()?)),
+            0xcb => visitor.visit_f64(f64::from_bits(self.u64()?)),
+            0xcc => visitor.visit_u64(self.u8()? as u64),
+            0xcd => visitor.visit_u64(self.u16()? as u64),
+            0xce => visitor.visit_u64(self.u32()? as u64),
+            0xcf => visitor.visit_u64(self.u64()?),
+            0xd0 => visitor.visit_i64(self.u8()? as i8 as i64),
+            0xd1 => visitor.visit_i64(self.u16()? as i16 as i64),
+            0xd2 => visitor.visit_i64(self.u32()? as i32 as i64),
+            0xd3 => visitor.visit_i64(self.u64()? as i64),
+            0xd9 => {
+                let len = self.u8()? as usize;
+                self.str(visitor, len)
+            }
+            0xda => {
+                let len = self.u16()? as usize;
+                self.str(visitor, len)
+            }
+            0xdb => {
+                let len = self.u32()? as usize;
+                self.str(visitor, len)
+            }
+            0xdc => {
+                let len = self.u16()? as usize;
+                self.seq(visitor, len)
+            }
+            0xdd => {
+                let len = self.u32()? as usize;
+                self.seq(visitor, len)
+            }
+            0xde => {
+                let len = self.u16()? as usize;
+                self.map(visitor, len)
+            }
+            0xdf => {
+                let len = self.u32()? as usize;
+                self.map(visitor, len)
+            }
+            m @ 0xe0..=0xff => visitor.visit_i64(m as i8 as i64),
+            // the unused marker and the extension types
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.slice.get(self.index) {
+            Some(0xc0) => {
+                self.index += 1;
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+/// The elements of an array, or the pairs of a map, left to read
+struct Access<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    left: usize,
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for Access<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.left {
+            0 => Ok(None),
+            _ => {
+                self.left -= 1;
+                seed.deserialize(&mut *self.de).map(Some)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.left)
+    }
+}
+
+impl<'a, 'de> de::MapAccess<'de> for Access<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.left {
+            0 => Ok(None),
+            _ => {
+                self.left -= 1;
+                seed.deserialize(&mut *self.de).map(Some)
+            }
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.left)
+    }
+}
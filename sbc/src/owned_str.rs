@@ -0,0 +1,129 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! A serde "with" module for the `[u8; N]` string fields of an owned struct.
+//!
+//! The owned struct cannot tell a string from a byte array by type alone, so
+//! the binding macro decorates every field which was a `&str` (or an array of
+//! `&str`) with `#[serde(with = "serde_bindgen_core::owned_str")]`. The bytes
+//! are treated as a NUL terminated string.
+
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{SerializeTuple, Serializer};
+
+use crate::SafeCopy;
+
+pub trait OwnedStr: Sized {
+    /// An all zero (empty string) value used to seed deserialization
+    const EMPTY: Self;
+
+    fn serialize_str<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    fn deserialize_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// Read the bytes up to the first NUL as a str (or all of them when there is no NUL)
+pub fn as_str(bytes: &[u8]) -> &str {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    core::str::from_utf8(&bytes[..end]).unwrap_or("")
+}
+
+/// Serialize an owned string field (or array of them) outside of a derive
+pub struct AsStr<'a, T>(pub &'a T);
+
+impl<'a, T: OwnedStr> serde::Serialize for AsStr<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_str(serializer)
+    }
+}
+
+impl<const N: usize> OwnedStr for [u8; N] {
+    const EMPTY: Self = [0; N];
+
+    fn serialize_str<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(as_str(self))
+    }
+
+    fn deserialize_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StrVisitor<const N: usize>;
+        impl<'de, const N: usize> Visitor<'de> for StrVisitor<N> {
+            type Value = [u8; N];
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a string")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(v.safe_copy())
+            }
+        }
+        deserializer.deserialize_str(StrVisitor::<N>)
+    }
+}
+
+impl<T: OwnedStr, const M: usize> OwnedStr for [T; M] {
+    const EMPTY: Self = [T::EMPTY; M];
+
+    fn serialize_str<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tup = serializer.serialize_tuple(M)?;
+        for item in self.iter() {
+            tup.serialize_element(&AsStr(item))?;
+        }
+        tup.end()
+    }
+
+    fn deserialize_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Item<T>(T);
+        impl<'de, T: OwnedStr> serde::Deserialize<'de> for Item<T> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                T::deserialize_str(deserializer).map(Item)
+            }
+        }
+        struct ArrayVisitor<T, const M: usize>(PhantomData<T>);
+        impl<'de, T: OwnedStr, const M: usize> Visitor<'de> for ArrayVisitor<T, M> {
+            type Value = [T; M];
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an array of length {}", M)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut ret = <[T; M]>::EMPTY;
+                for (i, slot) in ret.iter_mut().enumerate() {
+                    match seq.next_element::<Item<T>>()? {
+                        Some(Item(item)) => *slot = item,
+                        None => return Err(Error::invalid_length(i, &self)),
+                    }
+                }
+                Ok(ret)
+            }
+        }
+        deserializer.deserialize_tuple(M, ArrayVisitor::<T, M>(PhantomData))
+    }
+}
+
+pub fn serialize<T: OwnedStr, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    value.serialize_str(serializer)
+}
+
+pub fn deserialize<'de, T: OwnedStr, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    T::deserialize_str(deserializer)
+}
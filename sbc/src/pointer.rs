@@ -0,0 +1,118 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Read and write a single value of an owned struct by RFC 6901 JSON Pointer,
+//! e.g. `/network/ip` or `/remotes/0/id`.
+//!
+//! The pointer is resolved to a path of field (or array) indexes with
+//! `Feed::step`, which names fields by their wire name. Values are written
+//! with the push parser, so writing a struct only changes the keys present in
+//! the value.
+
+use crate::feed::{Feed, FeedState, Status, DEPTH, KEY_LEN};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The pointer does not address a value of this struct
+    NotFound,
+    /// The value is invalid, or does not fit the field
+    Invalid,
+    /// The buffer is too small for the printed value
+    Full,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Implemented by `#[binding]` for each owned struct
+pub trait Pointer: Feed {
+    /// Print the value at path into bytes
+    fn get(&self, path: &[u16], bytes: &mut [u8]) -> Result<usize>;
+}
+
+/// Print value into bytes as JSON
+pub fn print<T: serde::Serialize + ?Sized>(value: &T, bytes: &mut [u8]) -> Result<usize> {
+    serde_json_core::to_slice(value, bytes).map_err(|_| Error::Full)
+}
+
+/// Resolve pointer into path, returning the depth of the path
+pub fn resolve<T: Pointer + ?Sized>(
+    root: &T,
+    pointer: &[u8],
+    path: &mut [u16; DEPTH],
+) -> Result<usize> {
+    if pointer.is_empty() {
+        return Ok(0);
+    }
+    if pointer[0] != b'/' {
+        return Err(Error::NotFound);
+    }
+    let mut depth = 0;
+    for token in pointer[1..].split(|b| *b == b'/') {
+        let mut buf = [0; KEY_LEN];
+        let token = unescape(token, &mut buf).ok_or(Error::NotFound)?;
+        if depth == DEPTH {
+            return Err(Error::NotFound);
+        }
+        path[depth] = root.step(&path[..depth], token).ok_or(Error::NotFound)?;
+        depth += 1;
+    }
+    Ok(depth)
+}
+
+/// Replace ~1 with / and ~0 with ~
+fn unescape<'a>(token: &[u8], buf: &'a mut [u8; KEY_LEN]) -> Option<&'a [u8]> {
+    let mut len = 0;
+    let mut iter = token.iter();
+    while let Some(b) = iter.next() {
+        let b = match b {
+            b'~' => match iter.next() {
+                Some(b'0') => b'~',
+                Some(b'1') => b'/',
+                _ => return None,
+            },
+            b => *b,
+        };
+        *buf.get_mut(len)? = b;
+        len += 1;
+    }
+    Some(&buf[..len])
+}
+
+/// Print the value at pointer into bytes
+pub fn get<T: Pointer + ?Sized>(src: &T, pointer: &[u8], bytes: &mut [u8]) -> Result<usize> {
+    let mut path = [0; DEPTH];
+    let depth = resolve(src, pointer, &mut path)?;
+    src.get(&path[..depth], bytes)
+}
+
+/// Parse value into the field at pointer. On error the field may be partly
+/// written.
+pub fn set<T: Pointer>(dst: &mut T, pointer: &[u8], value: &[u8]) -> Result<()> {
+    let mut path = [0; DEPTH];
+    let depth = resolve(dst, pointer, &mut path)?;
+    let mut state = FeedState::at(dst, &path[..depth]).ok_or(Error::NotFound)?;
+    let status = match state.feed(dst, value) {
+        Status::NeedMore => state.finish(dst),
+        status => status,
+    };
+    match status {
+        Status::Complete(n) if value[n..].iter().all(|b| b.is_ascii_whitespace()) => Ok(()),
+        _ => Err(Error::Invalid),
+    }
+}
@@ -0,0 +1,409 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Protocol Buffers (proto3) encoding of the owned structs.
+//!
+//! `#[binding]` implements [`Message`] for each owned struct, field by field,
+//! with the field numbers from `sbc: tag` (or the position of the field).
+//! Integers are varints (`uint32`, or zigzag `sint32` when signed), strings
+//! and nested structs are length delimited, and arrays are repeated fields,
+//! flattened in row major order. Arrays of numbers are packed. The schema
+//! sbc-gen writes with `-f proto` describes the same encoding.
+//!
+//! Encoding skips zero numbers and empty strings, as proto3 does, but writes
+//! every element of an array so a reader sees its fixed length. Decoding
+//! expects a zeroed struct, accepts packed or unpacked arrays in any number
+//! of pieces, skips unknown fields and fails on anything which does not fit.
+
+use core::str;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The output buffer is too small
+    BufferFull,
+    /// The input ends in the middle of a value
+    Eof,
+    /// The input is not protobuf, or does not fit the struct
+    Invalid,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireType {
+    Varint = 0,
+    I64 = 1,
+    Len = 2,
+    I32 = 5,
+}
+
+/// Implemented by `#[binding]` for each owned struct
+pub trait Message {
+    /// Write every field
+    fn encode(&self, enc: &mut Encoder<'_>) -> Result<()>;
+
+    /// Read fields until the input ends
+    fn decode(&mut self, dec: &mut Decoder<'_>) -> Result<()>;
+}
+
+/// A number which is written as a varint
+pub trait Scalar: Copy {
+    fn to_varint(self) -> u64;
+    fn from_varint(varint: u64) -> Result<Self>;
+}
+
+macro_rules! impl_scalar_unsigned {
+    ($t:ty) => {
+        impl Scalar for $t {
+            fn to_varint(self) -> u64 {
+                self as u64
+            }
+
+            fn from_varint(varint: u64) -> Result<$t> {
+                <$t>::try_from(varint).map_err(|_| Error::Invalid)
+            }
+        }
+    };
+}
+
+macro_rules! impl_scalar_signed {
+    ($t:ty) => {
+        impl Scalar for $t {
+            // zigzag, so small negative numbers stay short
+            fn to_varint(self) -> u64 {
+                let n = self as i32;
+                ((n << 1) ^ (n >> 31)) as u32 as u64
+            }
+
+            fn from_varint(varint: u64) -> Result<$t> {
+                let n = u32::try_from(varint).map_err(|_| Error::Invalid)?;
+                let n = ((n >> 1) as i32) ^ -((n & 1) as i32);
+                <$t>::try_from(n).map_err(|_| Error::Invalid)
+            }
+        }
+    };
+}
+
+impl_scalar_unsigned!(u8);
+impl_scalar_unsigned!(u16);
+impl_scalar_unsigned!(u32);
+impl_scalar_signed!(i8);
+impl_scalar_signed!(i16);
+impl_scalar_signed!(i32);
+
+impl Scalar for bool {
+    fn to_varint(self) -> u64 {
+        self as u64
+    }
+
+    fn from_varint(varint: u64) -> Result<bool> {
+        Ok(varint != 0)
+    }
+}
+
+/// The number of bytes a varint takes
+pub const fn varint_len(mut varint: u64) -> usize {
+    let mut len = 1;
+    while varint >= 0x80 {
+        varint >>= 7;
+        len += 1;
+    }
+    len
+}
+
+/// The number of bytes a length delimited field takes, given the length of
+/// its key and of its value
+pub const fn delimited_len(key: usize, len: usize) -> usize {
+    key + varint_len(len as u64) + len
+}
+
+/// The bytes of a string field up to the NUL terminator
+fn until_nul(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    &bytes[..end]
+}
+
+/// Writes into a buffer, or only counts when there is none
+pub struct Encoder<'a> {
+    buf: Option<&'a mut [u8]>,
+    len: usize,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Encoder<'a> {
+        Encoder {
+            buf: Some(buf),
+            len: 0,
+        }
+    }
+
+    /// The number of bytes written (or counted) so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        if let Some(buf) = &mut self.buf {
+            buf.get_mut(self.len..self.len + bytes.len())
+                .ok_or(Error::BufferFull)?
+                .copy_from_slice(bytes);
+        }
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    fn varint(&mut self, mut varint: u64) -> Result<()> {
+        while varint >= 0x80 {
+            self.write(&[varint as u8 | 0x80])?;
+            varint >>= 7;
+        }
+        self.write(&[varint as u8])
+    }
+
+    fn key(&mut self, tag: u32, wire: WireType) -> Result<()> {
+        self.varint(((tag as u64) << 3) | wire as u64)
+    }
+
+    fn delimited(&mut self, tag: u32, bytes: &[u8]) -> Result<()> {
+        self.key(tag, WireType::Len)?;
+        self.varint(bytes.len() as u64)?;
+        self.write(bytes)
+    }
+
+    /// A number, skipped when zero
+    pub fn scalar<T: Scalar>(&mut self, tag: u32, value: T) -> Result<()> {
+        match value.to_varint() {
+            0 => Ok(()),
+            varint => {
+                self.key(tag, WireType::Varint)?;
+                self.varint(varint)
+            }
+        }
+    }
+
+    /// Every number of an array, packed
+    pub fn packed<T: Scalar>(&mut self, tag: u32, values: &[T]) -> Result<()> {
+        let len = values
+            .iter()
+            .map(|value| varint_len(value.to_varint()))
+            .sum::<usize>();
+        self.key(tag, WireType::Len)?;
+        self.varint(len as u64)?;
+        values
+            .iter()
+            .try_for_each(|value| self.varint(value.to_varint()))
+    }
+
+    /// A NUL terminated string, skipped when empty
+    pub fn string(&mut self, tag: u32, value: &[u8]) -> Result<()> {
+        match until_nul(value) {
+            [] => Ok(()),
+            bytes => self.delimited(tag, bytes),
+        }
+    }
+
+    /// Every string of an array, empty ones included
+    pub fn strings<const N: usize>(&mut self, tag: u32, values: &[[u8; N]]) -> Result<()> {
+        values
+            .iter()
+            .try_for_each(|value| self.delimited(tag, until_nul(value)))
+    }
+
+    /// A nested struct
+    pub fn message<T: Message>(&mut self, tag: u32, value: &T) -> Result<()> {
+        let len = encoded_len(value)?;
+        self.key(tag, WireType::Len)?;
+        self.varint(len as u64)?;
+        value.encode(self)
+    }
+
+    /// Every struct of an array
+    pub fn messages<T: Message>(&mut self, tag: u32, values: &[T]) -> Result<()> {
+        values.iter().try_for_each(|value| self.message(tag, value))
+    }
+}
+
+/// Reads the fields of one message
+pub struct Decoder<'a> {
+    slice: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(slice: &'a [u8]) -> Decoder<'a> {
+        Decoder { slice, index: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.index.checked_add(len).ok_or(Error::Eof)?;
+        let bytes = self.slice.get(self.index..end).ok_or(Error::Eof)?;
+        self.index = end;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut varint = 0u64;
+        for shift in (0..70).step_by(7) {
+            let byte = self.take(1)?[0];
+            varint |= ((byte & 0x7f) as u64).checked_shl(shift).unwrap_or(0);
+            if byte & 0x80 == 0 {
+                return Ok(varint);
+            }
+        }
+        Err(Error::Invalid)
+    }
+
+    fn delimited(&mut self, wire: WireType) -> Result<&'a [u8]> {
+        match wire {
+            WireType::Len => {
+                let len = usize::try_from(self.varint()?).map_err(|_| Error::Invalid)?;
+                self.take(len)
+            }
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    /// The tag and wire type of the next field, or none at the end
+    pub fn key(&mut self) -> Result<Option<(u32, WireType)>> {
+        if self.index == self.slice.len() {
+            return Ok(None);
+        }
+        let key = self.varint()?;
+        let wire = match key & 7 {
+            0 => WireType::Varint,
+            1 => WireType::I64,
+            2 => WireType::Len,
+            5 => WireType::I32,
+            _ => return Err(Error::Invalid),
+        };
+        match u32::try_from(key >> 3) {
+            Ok(tag) if tag > 0 => Ok(Some((tag, wire))),
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    /// Step over a field the struct does not have
+    pub fn skip(&mut self, wire: WireType) -> Result<()> {
+        match wire {
+            WireType::Varint => self.varint().map(|_| ()),
+            WireType::I64 => self.take(8).map(|_| ()),
+            WireType::Len => self.delimited(wire).map(|_| ()),
+            WireType::I32 => self.take(4).map(|_| ()),
+        }
+    }
+
+    pub fn scalar<T: Scalar>(&mut self, wire: WireType, dst: &mut T) -> Result<()> {
+        match wire {
+            WireType::Varint => {
+                *dst = T::from_varint(self.varint()?)?;
+                Ok(())
+            }
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    /// The next numbers of an array, packed or one at a time
+    pub fn packed<T: Scalar>(
+        &mut self,
+        wire: WireType,
+        dst: &mut [T],
+        count: &mut usize,
+    ) -> Result<()> {
+        let mut push = |varint: u64| {
+            *dst.get_mut(*count).ok_or(Error::Invalid)? = T::from_varint(varint)?;
+            *count += 1;
+            Ok(())
+        };
+        match wire {
+            WireType::Varint => push(self.varint()?),
+            WireType::Len => {
+                let mut packed = Decoder::new(self.delimited(wire)?);
+                while packed.index < packed.slice.len() {
+                    push(packed.varint()?)?;
+                }
+                Ok(())
+            }
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    /// A string, which must leave room for the NUL terminator
+    pub fn string(&mut self, wire: WireType, dst: &mut [u8]) -> Result<()> {
+        let bytes = self.delimited(wire)?;
+        str::from_utf8(bytes).map_err(|_| Error::Invalid)?;
+        if bytes.len() >= dst.len() {
+            return Err(Error::Invalid);
+        }
+        dst[..bytes.len()].copy_from_slice(bytes);
+        dst[bytes.len()..].fill(0);
+        Ok(())
+    }
+
+    /// The next string of an array
+    pub fn strings<const N: usize>(
+        &mut self,
+        wire: WireType,
+        dst: &mut [[u8; N]],
+        count: &mut usize,
+    ) -> Result<()> {
+        self.string(wire, dst.get_mut(*count).ok_or(Error::Invalid)?)?;
+        *count += 1;
+        Ok(())
+    }
+
+    /// A nested struct
+    pub fn message<T: Message>(&mut self, wire: WireType, dst: &mut T) -> Result<()> {
+        dst.decode(&mut Decoder::new(self.delimited(wire)?))
+    }
+
+    /// The next struct of an array
+    pub fn messages<T: Message>(
+        &mut self,
+        wire: WireType,
+        dst: &mut [T],
+        count: &mut usize,
+    ) -> Result<()> {
+        self.message(wire, dst.get_mut(*count).ok_or(Error::Invalid)?)?;
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// The number of bytes value encodes to
+pub fn encoded_len<T: Message>(value: &T) -> Result<usize> {
+    let mut sizer = Encoder { buf: None, len: 0 };
+    value.encode(&mut sizer)?;
+    Ok(sizer.len)
+}
+
+/// Encode value into buf, returning the number of bytes written
+pub fn encode<T: Message>(value: &T, buf: &mut [u8]) -> Result<usize> {
+    let mut enc = Encoder::new(buf);
+    value.encode(&mut enc)?;
+    Ok(enc.len)
+}
+
+/// Decode bytes into dst, which should be zeroed first
+pub fn decode<T: Message>(dst: &mut T, bytes: &[u8]) -> Result<()> {
+    dst.decode(&mut Decoder::new(bytes))
+}
@@ -0,0 +1,212 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! A parse and print engine driven by the descriptor tables.
+//!
+//! With `#[binding(mode = "table")]` a struct gets no serde impls of its own.
+//! Its generated functions hand their descriptor to this engine instead, so
+//! every bound struct shares one copy of the code. It is a little slower, but
+//! much smaller when there are many bound structs.
+//!
+//! Table mode is a separate API, not the serde mode with a smaller engine.
+//! The struct keeps its owned `#[repr(C)]` form with `Default`, `Init`, the
+//! `From` conversions, the weight, layout and ABI constants and, with the
+//! `protobuf` feature, its protobuf encoding. The only bindings are init,
+//! descriptor, parse_owned, print_owned, abi_hash, pb_encode and pb_decode,
+//! and parse_owned and print_owned need the descriptor.
+//!
+//! Its parse merges the document into the struct, as the merge binding of
+//! the serde mode does. Missing keys keep their value rather than failing
+//! the parse, so call init first to parse into a fresh struct.
+
+use serde::ser::{Serialize, SerializeStruct, SerializeTuple, Serializer};
+
+use crate::descriptor::{Descriptor, FieldDescriptor, Kind};
+use crate::feed::{index, Feed, FeedState, Slot, Status};
+use crate::owned_str::as_str;
+
+/// Serializes the owned struct at ptr, as described by desc
+struct Table {
+    desc: &'static Descriptor,
+    ptr: *const u8,
+}
+
+impl Serialize for Table {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct(self.desc.name(), self.desc.fields().len())?;
+        for field in self.desc.fields() {
+            let value = Elements {
+                field,
+                ptr: unsafe { self.ptr.add(field.offset()) },
+                dims: field.dims(),
+            };
+            s.serialize_field(field.wire_name(), &value)?;
+        }
+        s.end()
+    }
+}
+
+/// Serializes the (array of) values of field at ptr
+struct Elements {
+    field: &'static FieldDescriptor,
+    ptr: *const u8,
+    dims: &'static [usize],
+}
+
+impl Serialize for Elements {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Some((n, dims)) = self.dims.split_first() {
+            let stride = dims.iter().product::<usize>() * self.field.size();
+            let mut s = serializer.serialize_tuple(*n)?;
+            for i in 0..*n {
+                let ptr = unsafe { self.ptr.add(i * stride) };
+                s.serialize_element(&Elements { ptr, dims, ..*self })?;
+            }
+            return s.end();
+        }
+        let ptr = self.ptr;
+        unsafe {
+            match self.field.kind() {
+                Kind::Bool => (*(ptr as *const bool)).serialize(serializer),
+                Kind::U8 => (*ptr).serialize(serializer),
+                Kind::I8 => (*(ptr as *const i8)).serialize(serializer),
+                Kind::U16 => (*(ptr as *const u16)).serialize(serializer),
+                Kind::I16 => (*(ptr as *const i16)).serialize(serializer),
+                Kind::U32 => (*(ptr as *const u32)).serialize(serializer),
+                Kind::I32 => (*(ptr as *const i32)).serialize(serializer),
+                Kind::Str => {
+                    let bytes = core::slice::from_raw_parts(ptr, self.field.capacity());
+                    serializer.serialize_str(as_str(bytes))
+                }
+                Kind::Struct => match self.field.nested() {
+                    Some(desc) => Table { desc, ptr }.serialize(serializer),
+                    None => serializer.serialize_unit(),
+                },
+            }
+        }
+    }
+}
+
+/// What lives at a path of the struct
+enum Place {
+    Struct(&'static Descriptor),
+    Array(usize),
+    Leaf(&'static FieldDescriptor),
+}
+
+/// Walk path from desc, returning what lives there and its byte offset
+fn locate(mut desc: &'static Descriptor, mut path: &[u16]) -> Option<(Place, usize)> {
+    let mut offset = 0;
+    loop {
+        let (i, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return Some((Place::Struct(desc), offset)),
+        };
+        let field = desc.fields().get(*i as usize)?;
+        offset += field.offset();
+        path = rest;
+        let mut dims = field.dims();
+        while let Some((n, inner)) = dims.split_first() {
+            let (j, rest) = match path.split_first() {
+                Some(split) => split,
+                None => return Some((Place::Array(*n), offset)),
+            };
+            if *j as usize >= *n {
+                return None;
+            }
+            offset += *j as usize * inner.iter().product::<usize>() * field.size();
+            path = rest;
+            dims = inner;
+        }
+        match field.kind() {
+            Kind::Struct => desc = field.nested()?,
+            _ if path.is_empty() => return Some((Place::Leaf(field), offset)),
+            _ => return None,
+        }
+    }
+}
+
+/// Feeds the owned struct at ptr, as described by desc
+struct TableMut {
+    desc: &'static Descriptor,
+    ptr: *mut u8,
+}
+
+impl Feed for TableMut {
+    fn step(&self, path: &[u16], token: &[u8]) -> Option<u16> {
+        match locate(self.desc, path)? {
+            (Place::Struct(desc), _) => {
+                let i = desc
+                    .fields()
+                    .iter()
+                    .position(|f| f.wire_name().as_bytes() == token);
+                i.map(|i| i as u16)
+            }
+            (Place::Array(n), _) => index(token, n),
+            (Place::Leaf(_), _) => None,
+        }
+    }
+
+    fn slot(&mut self, path: &[u16]) -> Slot<'_> {
+        let (field, ptr) = match locate(self.desc, path) {
+            Some((Place::Struct(_), _)) => return Slot::Object,
+            Some((Place::Array(n), _)) => return Slot::Array(n),
+            Some((Place::Leaf(field), offset)) => (field, unsafe { self.ptr.add(offset) }),
+            None => return Slot::Skip,
+        };
+        unsafe {
+            match field.kind() {
+                Kind::Bool => Slot::Bool(&mut *(ptr as *mut bool)),
+                Kind::U8 => Slot::U8(&mut *ptr),
+                Kind::I8 => Slot::I8(&mut *(ptr as *mut i8)),
+                Kind::U16 => Slot::U16(&mut *(ptr as *mut u16)),
+                Kind::I16 => Slot::I16(&mut *(ptr as *mut i16)),
+                Kind::U32 => Slot::U32(&mut *(ptr as *mut u32)),
+                Kind::I32 => Slot::I32(&mut *(ptr as *mut i32)),
+                Kind::Str => Slot::Str(core::slice::from_raw_parts_mut(ptr, field.capacity())),
+                Kind::Struct => Slot::Skip,
+            }
+        }
+    }
+}
+
+/// Print the owned struct at src as JSON into bytes.
+///
+/// # Safety
+/// src must point at the owned struct described by desc
+pub unsafe fn print(
+    desc: &'static Descriptor,
+    src: *const u8,
+    bytes: &mut [u8],
+) -> serde_json_core::ser::Result<usize> {
+    serde_json_core::to_slice(&Table { desc, ptr: src }, bytes)
+}
+
+/// Parse a JSON document into the owned struct at dst, returning the number of
+/// bytes consumed. Fields missing from the document keep their value.
+///
+/// # Safety
+/// dst must point at the owned struct described by desc
+pub unsafe fn parse(desc: &'static Descriptor, dst: *mut u8, bytes: &[u8]) -> Option<usize> {
+    let mut table = TableMut { desc, ptr: dst };
+    match FeedState::new().feed(&mut table, bytes) {
+        Status::Complete(n) => Some(n),
+        _ => None,
+    }
+}
@@ -0,0 +1,518 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! Serialize JSON into a writer instead of a buffer.
+//!
+//! The output is identical to `serde_json_core::to_slice`, but is pushed to
+//! the writer as it is produced so the caller never needs a `MAX_LEN` buffer.
+
+use core::fmt;
+use serde::ser::{self, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The writer refused the bytes
+    Write,
+    /// The type contains something we do not serialize (floats, chars, etc)
+    Unsupported,
+    /// A Serialize implementation reported an error
+    Custom,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Write => write!(f, "writer error"),
+            Error::Unsupported => write!(f, "unsupported type"),
+            Error::Custom => write!(f, "serialize error"),
+        }
+    }
+}
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(_msg: T) -> Self {
+        Error::Custom
+    }
+}
+
+/// Something which accepts the serialized bytes
+pub trait Write {
+    fn write(&mut self, bytes: &[u8]) -> Result<()>;
+}
+
+/// Adapt a `core::fmt::Write`
+pub struct FmtWriter<'a, W: fmt::Write>(pub &'a mut W);
+
+impl<'a, W: fmt::Write> Write for FmtWriter<'a, W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        // We only ever write whole utf8 sequences
+        let s = core::str::from_utf8(bytes).map_err(|_| Error::Write)?;
+        self.0.write_str(s).map_err(|_| Error::Write)
+    }
+}
+
+/// Adapt an `embedded_io::Write`
+#[cfg(feature = "embedded-io")]
+pub struct IoWriter<'a, W: embedded_io::Write>(pub &'a mut W);
+
+#[cfg(feature = "embedded-io")]
+impl<'a, W: embedded_io::Write> Write for IoWriter<'a, W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.0.write_all(bytes).map_err(|_| Error::Write)
+    }
+}
+
+/// A "C" write callback. Return 0 to continue, anything else to abort.
+pub type WriteFn = extern "C" fn(ctx: *mut core::ffi::c_void, bytes: *const u8, len: usize) -> i32;
+
+/// Adapt a "C" write callback and its context pointer
+pub struct CallbackWriter {
+    pub write: WriteFn,
+    pub ctx: *mut core::ffi::c_void,
+}
+
+impl Write for CallbackWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        match (self.write)(self.ctx, bytes.as_ptr(), bytes.len()) {
+            0 => Ok(()),
+            _ => Err(Error::Write),
+        }
+    }
+}
+
+pub struct Serializer<'a, W: Write> {
+    writer: &'a mut W,
+    len: usize,
+}
+
+impl<'a, W: Write> Serializer<'a, W> {
+    pub fn new(writer: &'a mut W) -> Serializer<'a, W> {
+        Serializer { writer, len: 0 }
+    }
+
+    /// Number of bytes written so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Result<()> {
+        if !bytes.is_empty() {
+            self.writer.write(bytes)?;
+            self.len += bytes.len();
+        }
+        Ok(())
+    }
+
+    fn push_unsigned(&mut self, mut v: u64) -> Result<()> {
+        let mut buf = [0u8; 20];
+        let mut i = buf.len();
+        loop {
+            i -= 1;
+            buf[i] = (v % 10) as u8 + b'0';
+            v /= 10;
+            if v == 0 {
+                break;
+            }
+        }
+        self.push(&buf[i..])
+    }
+
+    fn push_signed(&mut self, v: i64) -> Result<()> {
+        if v < 0 {
+            self.push(b"-")?;
+        }
+        self.push_unsigned(v.unsigned_abs())
+    }
+
+    fn push_str(&mut self, v: &str) -> Result<()> {
+        self.push(b"\"")?;
+        self.push_escaped(v)?;
+        self.push(b"\"")
+    }
+
+    fn push_escaped(&mut self, v: &str) -> Result<()> {
+        let bytes = v.as_bytes();
+        let mut start = 0;
+        for (i, &b) in bytes.iter().enumerate() {
+            let escape: &[u8] = match b {
+                b'\\' => b"\\\\",
+                b'"' => b"\\\"",
+                0x08 => b"\\b",
+                b'\t' => b"\\t",
+                b'\n' => b"\\n",
+                0x0C => b"\\f",
+                b'\r' => b"\\r",
+                0x00..=0x1F => &[],
+                _ => continue,
+            };
+            self.push(&bytes[start..i])?;
+            if escape.is_empty() {
+                const HEX: &[u8; 16] = b"0123456789ABCDEF";
+                self.push(&[
+                    b'\\',
+                    b'u',
+                    b'0',
+                    b'0',
+                    HEX[(b >> 4) as usize],
+                    HEX[(b & 0xF) as usize],
+                ])?;
+            } else {
+                self.push(escape)?;
+            }
+            start = i + 1;
+        }
+        self.push(&bytes[start..])
+    }
+}
+
+// Escape the pieces of a Display impl as they are formatted
+impl<'a, W: Write> fmt::Write for Serializer<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_escaped(s).map_err(|_| fmt::Error)
+    }
+}
+
+/// Serialize value into writer, returning the number of bytes written
+pub fn to_writer<W, T>(value: &T, writer: &mut W) -> Result<usize>
+where
+    W: Write,
+    T: Serialize + ?Sized,
+{
+    let mut ser = Serializer::new(writer);
+    value.serialize(&mut ser)?;
+    Ok(ser.len())
+}
+
+/// Serialize value into a `core::fmt::Write`
+pub fn to_fmt<W, T>(value: &T, writer: &mut W) -> Result<usize>
+where
+    W: fmt::Write,
+    T: Serialize + ?Sized,
+{
+    to_writer(value, &mut FmtWriter(writer))
+}
+
+/// Serialize value into an `embedded_io::Write`
+#[cfg(feature = "embedded-io")]
+pub fn to_io<W, T>(value: &T, writer: &mut W) -> Result<usize>
+where
+    W: embedded_io::Write,
+    T: Serialize + ?Sized,
+{
+    to_writer(value, &mut IoWriter(writer))
+}
+
+/// Serialize value into a "C" write callback
+pub fn to_callback<T>(value: &T, write: WriteFn, ctx: *mut core::ffi::c_void) -> Result<usize>
+where
+    T: Serialize + ?Sized,
+{
+    to_writer(value, &mut CallbackWriter { write, ctx })
+}
+
+impl<'a, 'w, W: Write> ser::Serializer for &'a mut Serializer<'w, W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a, 'w, W>;
+    type SerializeTuple = Compound<'a, 'w, W>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = Compound<'a, 'w, W>;
+    type SerializeStruct = Compound<'a, 'w, W>;
+    type SerializeStructVariant = Compound<'a, 'w, W>;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.push(if v { b"true" } else { b"false" })
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.push_signed(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.push_signed(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.push_signed(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.push_signed(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.push_unsigned(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.push_unsigned(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.push_unsigned(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.push_unsigned(v)
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<()> {
+        Err(Error::Unsupported)
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<()> {
+        Err(Error::Unsupported)
+    }
+
+    fn serialize_char(self, _v: char) -> Result<()> {
+        Err(Error::Unsupported)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.push_str(v)
+    }
+
+    fn collect_str<T: fmt::Display + ?Sized>(self, value: &T) -> Result<()> {
+        self.push(b"\"")?;
+        fmt::write(&mut *self, format_args!("{}", value)).map_err(|_| Error::Write)?;
+        self.push(b"\"")
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.push(v)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.push(b"null")
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.push(b"null")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.push_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.push(b"{")?;
+        self.push_str(variant)?;
+        self.push(b":")?;
+        value.serialize(&mut *self)?;
+        self.push(b"}")
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        self.push(b"[")?;
+        Ok(Compound::new(self, b"]"))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(Error::Unsupported)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(Error::Unsupported)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        self.push(b"{")?;
+        Ok(Compound::new(self, b"}"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        self.push(b"{")?;
+        Ok(Compound::new(self, b"}"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.push(b"{")?;
+        self.push_str(variant)?;
+        self.push(b":{")?;
+        Ok(Compound::new(self, b"}}"))
+    }
+}
+
+/// Shared state for arrays, maps and structs. Tracks the comma.
+pub struct Compound<'a, 'w, W: Write> {
+    ser: &'a mut Serializer<'w, W>,
+    first: bool,
+    close: &'static [u8],
+}
+
+impl<'a, 'w, W: Write> Compound<'a, 'w, W> {
+    fn new(ser: &'a mut Serializer<'w, W>, close: &'static [u8]) -> Compound<'a, 'w, W> {
+        Compound {
+            ser,
+            first: true,
+            close,
+        }
+    }
+
+    fn comma(&mut self) -> Result<()> {
+        if !self.first {
+            self.ser.push(b",")?;
+        }
+        self.first = false;
+        Ok(())
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.comma()?;
+        self.ser.push_str(key)?;
+        self.ser.push(b":")?;
+        value.serialize(&mut *self.ser)
+    }
+}
+
+impl<'a, 'w, W: Write> ser::SerializeSeq for Compound<'a, 'w, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.comma()?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        self.ser.push(self.close)
+    }
+}
+
+impl<'a, 'w, W: Write> ser::SerializeTuple for Compound<'a, 'w, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<()> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<'a, 'w, W: Write> ser::SerializeMap for Compound<'a, 'w, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.comma()?;
+        key.serialize(&mut *self.ser)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.ser.push(b":")?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<()> {
+        self.ser.push(self.close)
+    }
+}
+
+impl<'a, 'w, W: Write> ser::SerializeStruct for Compound<'a, 'w, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.ser.push(self.close)
+    }
+}
+
+impl<'a, 'w, W: Write> ser::SerializeStructVariant for Compound<'a, 'w, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.ser.push(self.close)
+    }
+}
@@ -24,14 +24,10 @@
 #![allow(unused)]
 
 // use sbc::serde;
-use serde;
 use serde_bindgen_core::binding;
-use serde_json_core;
 
 mod songs {
-    use serde;
     use serde_bindgen_core::binding;
-    use serde_json_core;
 
     #[derive(Debug, PartialEq, Clone)]
     #[binding]
@@ -0,0 +1,67 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::{binding, Binding};
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 8
+    pub id0: &'a str,
+}
+
+#[binding(prefix = "test")]
+pub struct Foo<'a> {
+    pub id0: u8,
+    pub id1: Remote<'a>,
+    /// sbc: len = 8
+    pub id2: &'a str,
+}
+
+#[binding(prefix = "test")]
+pub struct Bar {
+    pub id0: u16,
+}
+
+// A generic "transport" that only knows about the Binding trait
+fn round_trip<T: Binding>(bytes: &[u8]) -> (T::Owned, usize) {
+    let mut buffer: [u8; 256] = [0; 256];
+    let (borrowed, _) = T::parse(bytes).unwrap();
+    let len = T::print(&borrowed, &mut buffer).unwrap();
+    assert!(len <= T::MAX_LEN);
+    assert_eq!(&buffer[0..len], bytes);
+    (T::to_owned(&borrowed), len)
+}
+
+#[test]
+fn can_be_generic_over_bindings() {
+    let (foo, len) = round_trip::<FooOwned>(br#"{"id0":3,"id1":{"id0":"abc"},"id2":"d"}"#);
+    assert_eq!(foo.id0, 3);
+    assert_eq!(&foo.id1.id0[0..4], b"abc\0");
+    assert_eq!(&foo.id2[0..2], b"d\0");
+    assert_eq!(len, 39);
+
+    let (bar, _) = round_trip::<BarOwned>(br#"{"id0":65535}"#);
+    assert_eq!(bar.id0, 65535);
+}
+
+#[test]
+fn can_read_max_len() {
+    assert_eq!(<FooOwned as Binding>::MAX_LEN, FOO_MAX_LEN);
+    assert_eq!(<BarOwned as Binding>::MAX_LEN, BAR_MAX_LEN);
+}
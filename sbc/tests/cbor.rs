@@ -0,0 +1,198 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::cbor::{self, Error};
+
+#[path = "common/device.rs"]
+mod device;
+
+use device::*;
+
+#[test]
+fn can_print_cbor() {
+    let mut bytes = [0; REMOTE_CBOR_MAX_LEN];
+    let mut len = bytes.len();
+    let rc = unsafe { test_cbor_print_remote(&remote("ab", -2), bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(rc, 0);
+    let mut expect = vec![0xa2, 0x66];
+    expect.extend(b"hostId");
+    expect.extend([0x62, b'a', b'b', 0x65]);
+    expect.extend(b"level");
+    expect.push(0x21);
+    assert_eq!(bytes[..len], expect);
+
+    let mut small = [0; 4];
+    let mut len = small.len();
+    let rc = unsafe { test_cbor_print_remote(&remote("ab", -2), small.as_mut_ptr(), &mut len) };
+    assert_eq!(rc, -1);
+}
+
+#[test]
+fn can_bound_cbor_len() {
+    assert_eq!(REMOTE_CBOR_MAX_LEN, 21);
+    assert_eq!(DEVICE_CBOR_MAX_LEN, 127);
+    let device = DeviceOwned {
+        name: *b"abcd\0",
+        enabled: true,
+        remote: remote("abc", i16::MIN),
+        grid: [[255; 2]; 2],
+        remotes: [remote("abc", i16::MIN), remote("abc", i16::MAX)],
+        tags: [*b"ab\0", *b"ab\0"],
+    };
+    let mut bytes = [0; DEVICE_CBOR_MAX_LEN];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_cbor_print_device(&device, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+    assert_eq!(len, DEVICE_CBOR_MAX_LEN);
+}
+
+#[test]
+fn can_round_trip_cbor() {
+    let mut device = DeviceOwned::default();
+    device.enabled = true;
+    device.remote = remote("abc", -300);
+    device.grid = [[1, 2], [3, 255]];
+    device.remotes[1] = remote("x", 7);
+    device.tags[0] = *b"hi\0";
+    let mut bytes = [0; DEVICE_CBOR_MAX_LEN];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_cbor_print_device(&device, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+
+    let mut decoded = DeviceOwned::default();
+    let rc = unsafe { test_cbor_parse_device(&mut decoded, bytes.as_ptr(), len) };
+    assert_eq!(rc, len as i32);
+    assert!(decoded.enabled);
+    assert_eq!(decoded.remote.host_id, *b"abc\0");
+    assert_eq!(decoded.remote.level, -300);
+    assert_eq!(decoded.grid, [[1, 2], [3, 255]]);
+    assert_eq!(decoded.remotes[1].host_id, *b"x\0\0\0");
+    assert_eq!(decoded.remotes[1].level, 7);
+    assert_eq!(decoded.tags, [*b"hi\0", [0; 3]]);
+
+    // truncated, or followed by more
+    assert_eq!(
+        unsafe { test_cbor_parse_device(&mut decoded, bytes.as_ptr(), len - 1) },
+        -1
+    );
+    assert_eq!(
+        unsafe { test_cbor_parse_device(&mut decoded, bytes.as_ptr(), len + 1) },
+        -1
+    );
+}
+
+#[test]
+fn can_parse_cbor_in_place() {
+    let device = DeviceOwned {
+        enabled: true,
+        remote: remote("abc", -300),
+        ..Default::default()
+    };
+    let mut bytes = [0; DEVICE_CBOR_MAX_LEN];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_cbor_print_device(&device, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+
+    // dst is written as the message is read, so a truncated one leaves the
+    // fields before the end
+    let mut decoded = DeviceOwned::default();
+    assert_eq!(
+        unsafe { test_cbor_parse_device(&mut decoded, bytes.as_ptr(), len - 1) },
+        -1
+    );
+    assert!(decoded.enabled);
+    assert_eq!(decoded.remote.level, -300);
+}
+
+#[test]
+fn can_parse_cbor_from_others() {
+    // an indefinite map, a wider number than needed, a tag and an unknown key
+    let mut bytes = vec![0xbf, 0x66];
+    bytes.extend(b"hostId");
+    bytes.extend([0xc0, 0x78, 2, b'a', b'b', 0x65]);
+    bytes.extend(b"level");
+    bytes.extend([0x3b, 0, 0, 0, 0, 0, 0, 0, 1]);
+    bytes.extend([0x61, b'x', 0x9f, 0xf6, 0xf9, 0x3c, 0x00, 0xff]);
+    bytes.push(0xff);
+    let (remote, len) = cbor::from_slice::<RemoteOwned>(&bytes).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(remote.host_id, *b"ab\0\0");
+    assert_eq!(remote.level, -2);
+
+    // a struct as an indefinite array of its fields
+    let bytes = [0x9f, 0x61, b'a', 0x05, 0xff];
+    let (remote, len) = cbor::from_slice::<RemoteOwned>(&bytes).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(remote.level, 5);
+}
+
+#[test]
+fn can_reject_cbor() {
+    // out of range
+    let bytes = [0x82, 0x61, b'a', 0x19, 0x80, 0x00];
+    assert!(cbor::from_slice::<RemoteOwned>(&bytes).is_err());
+    // too many elements
+    let bytes = [0x83, 0x61, b'a', 0x05, 0x05];
+    assert_eq!(
+        cbor::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Invalid)
+    );
+    let bytes = [0x9f, 0x61, b'a', 0x05, 0x05, 0xff];
+    assert_eq!(
+        cbor::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Invalid)
+    );
+    // not utf-8
+    let bytes = [0x82, 0x61, 0xff, 0x05];
+    assert_eq!(
+        cbor::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Invalid)
+    );
+    // an indefinite length string
+    let bytes = [0x82, 0x7f, 0x61, b'a', 0xff, 0x05];
+    assert_eq!(
+        cbor::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Invalid)
+    );
+    // nested too deep, under an unknown key
+    let mut bytes = vec![0xa1, 0x61, b'x'];
+    bytes.extend([0x81; 40]);
+    bytes.push(0x05);
+    assert_eq!(
+        cbor::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Invalid)
+    );
+    // truncated
+    let bytes = [0x82, 0x64, b'a'];
+    assert_eq!(
+        cbor::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Eof)
+    );
+    let bytes = [0x9f, 0x61, b'a', 0x05];
+    assert_eq!(
+        cbor::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Eof)
+    );
+}
@@ -0,0 +1,99 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::chunk::{self, ChunkState};
+
+#[path = "common/foo.rs"]
+mod foo;
+
+use foo::*;
+
+fn expect(owned: &FooOwned) -> Vec<u8> {
+    let mut bytes: [u8; 512] = [0; 512];
+    let mut len = 512;
+    let ret = unsafe { test_print_foo_owned(owned, bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(ret, 0);
+    bytes[0..len].to_vec()
+}
+
+#[test]
+fn can_print_chunks_of_any_size() {
+    let owned = owned();
+    let expect = expect(&owned);
+    for size in 1..expect.len() + 2 {
+        let mut state = ChunkState::new();
+        let mut buf = vec![0; size];
+        let mut out = Vec::new();
+        loop {
+            let ret =
+                unsafe { test_print_foo_owned_chunk(&mut state, &owned, buf.as_mut_ptr(), size) };
+            assert!(ret >= 0);
+            if ret == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[0..ret as usize]);
+        }
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            std::str::from_utf8(&expect).unwrap()
+        );
+    }
+}
+
+#[test]
+fn can_print_chunk_after_done() {
+    let owned = owned();
+    let mut state = ChunkState::new();
+    let mut buf = [0; 512];
+    let len = chunk::print_chunk(&owned, &mut state, &mut buf).unwrap();
+    assert_eq!(&buf[0..len], expect(&owned).as_slice());
+    assert!(state.done);
+    assert_eq!(chunk::print_chunk(&owned, &mut state, &mut buf), Ok(0));
+}
+
+#[test]
+fn can_not_print_chunk_into_nothing() {
+    let owned = owned();
+    let mut state = ChunkState::new();
+    let ret = unsafe { test_print_foo_owned_chunk(&mut state, &owned, [0u8; 0].as_mut_ptr(), 0) };
+    assert_eq!(ret, -1);
+}
+
+struct Long(Vec<u8>);
+
+impl chunk::Chunk for Long {
+    fn chunk(&self, c: &mut chunk::Chunker, depth: usize) -> Result<(), chunk::Stop> {
+        chunk::array(c, depth, &self.0, |c, b, _| c.leaf(b))
+    }
+}
+
+#[test]
+fn can_print_chunks_of_long_arrays() {
+    let long = Long(vec![7; 40000]);
+    let mut state = ChunkState::new();
+    let mut buf = [0; 4096];
+    let mut out = Vec::new();
+    loop {
+        match chunk::print_chunk(&long, &mut state, &mut buf).unwrap() {
+            0 => break,
+            n => out.extend_from_slice(&buf[..n]),
+        }
+    }
+    assert_eq!(out, format!("[{}]", ["7"; 40000].join(",")).into_bytes());
+}
@@ -17,37 +17,29 @@
 // @author Thomas Chiantia <thomas.chiantia@gmail.com>
 // @date 2022
 
-#![no_builtins]
-#![feature(lang_items)]
-#![cfg_attr(not(test), no_std)]
-#![allow(dead_code)]
-#![allow(unused)]
+//! A device with nested and repeated remotes, for the binary encodings
 
-// use data::serde;
 use serde_bindgen_core::binding;
-use serde_bindgen_core::serde;
-use serde_bindgen_core::serde_json_core;
 
-#[binding]
-pub struct Bar<'a> {
-    /// sbc: default = "hi"
-    /// sbc: len = 3
-    id0: &'a str,
+#[binding(prefix = "test", rename_all = "camelCase")]
+pub struct Remote<'a> {
+    /// sbc: len = 4
+    pub host_id: &'a str,
+    pub level: i16,
 }
 
-#[binding(prefix = "foo")]
-pub struct Foo<'a> {
-    /// sbc: default = 12
-    id0: u8,
-    id1: Bar<'a>,
-    id2: [Bar<'a>; 3],
-    /// sbc: default = 3
-    id3: [u32; 3],
-    /// sbc: default = "hello"
-    /// sbc: len = 12
-    id4: &'a str,
-    /// junk
-    id5: &'a str,
+#[binding(prefix = "test")]
+pub struct Device<'a> {
+    /// sbc: len = 5
+    pub name: &'a str,
+    pub enabled: bool,
+    pub remote: Remote<'a>,
+    pub grid: [[u8; 2]; 2],
+    pub remotes: [Remote<'a>; 2],
+    /// sbc: len = 3
+    pub tags: [&'a str; 2],
 }
 
-fn main() {}
+pub fn remote(host_id: &str, level: i16) -> RemoteOwned {
+    RemoteOwned::from(&Remote { host_id, level })
+}
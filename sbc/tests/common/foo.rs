@@ -0,0 +1,73 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+//! A struct of every kind of field, and its value, for the resumable printer
+//! and parser
+
+use serde_bindgen_core::binding;
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 16
+    pub id0: &'a str,
+    pub id1: [i32; 2],
+}
+
+#[binding(prefix = "test", rename_all = "camelCase")]
+pub struct Foo<'a> {
+    pub id_zero: u8,
+    pub id_one: i32,
+    pub id_two: bool,
+    /// sbc: len = 16
+    pub id_three: &'a str,
+    pub id_four: Remote<'a>,
+    pub id_five: [Remote<'a>; 2],
+    /// sbc: len = 8
+    pub id_six: [[&'a str; 2]; 2],
+    pub id_seven: [u8; 0],
+}
+
+pub const ROOT: Foo = Foo {
+    id_zero: 255,
+    id_one: -2147483648,
+    id_two: true,
+    id_three: "esc \"\\\n",
+    id_four: Remote {
+        id0: "a",
+        id1: [0, -1],
+    },
+    id_five: [
+        Remote {
+            id0: "b",
+            id1: [1, 2],
+        },
+        Remote {
+            id0: "c",
+            id1: [3, 4],
+        },
+    ],
+    id_six: [["d", "e"], ["f", "g"]],
+    id_seven: [],
+};
+
+pub fn owned() -> FooOwned {
+    let mut owned = FooOwned::default();
+    test_copy_foo(&mut owned, &ROOT);
+    owned
+}
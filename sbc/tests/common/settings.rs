@@ -17,12 +17,33 @@
 // @author Thomas Chiantia <thomas.chiantia@gmail.com>
 // @date 2022
 
-use syn::parse::{Parse, ParseStream, Peek, Result};
+//! Settings with defaults, for updating one struct from another
 
-pub fn maybe<T: Peek, R: Parse>(token: T, input: &mut ParseStream) -> Result<Option<R>> {
-    if input.peek(token) {
-        Ok(Some(input.parse()?))
-    } else {
-        Ok(None)
-    }
+use serde_bindgen_core::binding;
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 8
+    pub id0: &'a str,
+    pub id1: [i32; 2],
+}
+
+#[binding(prefix = "test", rename_all = "camelCase")]
+pub struct Settings<'a> {
+    /// sbc: len = 8
+    /// sbc: default = "admin"
+    pub user_name: &'a str,
+    /// sbc: default = 80
+    pub port: u16,
+    pub enabled: bool,
+    pub remote: Remote<'a>,
+    pub remotes: [Remote<'a>; 2],
+    /// sbc: len = 4
+    pub tags: [&'a str; 2],
+}
+
+pub fn init() -> SettingsOwned {
+    let mut owned = SettingsOwned::default();
+    test_init_settings(&mut owned);
+    owned
 }
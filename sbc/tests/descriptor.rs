@@ -0,0 +1,126 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+
+use serde_bindgen_core::descriptor::{Descriptor, FieldDescriptor, Kind};
+
+#[binding(prefix = "test")]
+pub struct Network<'a> {
+    /// sbc: len = 16
+    pub ip: &'a str,
+    pub port: u16,
+}
+
+#[binding(prefix = "test", rename_all = "camelCase")]
+pub struct Device<'a> {
+    /// sbc: len = 8
+    pub host_name: &'a str,
+    pub network: Network<'a>,
+    pub ports: [Network<'a>; 2],
+    pub grid: [[i16; 3]; 2],
+    /// sbc: len = 4
+    pub tags: [&'a str; 2],
+    pub enabled: bool,
+}
+
+#[binding(prefix = "test", skip = ["descriptor"])]
+pub struct Opaque {
+    pub id: u8,
+}
+
+extern "C" {
+    static test_descriptor_network: Descriptor;
+    static test_descriptor_network_fields: [FieldDescriptor; 2];
+}
+
+fn find(descriptor: &Descriptor, name: &str) -> (usize, Kind, usize, usize) {
+    let field = descriptor
+        .fields()
+        .iter()
+        .find(|f| f.name() == name)
+        .unwrap();
+    (
+        field.offset(),
+        field.kind(),
+        field.count(),
+        field.capacity(),
+    )
+}
+
+#[test]
+fn can_describe_fields() {
+    let descriptor = &DEVICE_DESCRIPTOR;
+    assert_eq!(descriptor.name(), "Device");
+    assert_eq!(descriptor.size(), std::mem::size_of::<DeviceOwned>());
+    let names: Vec<_> = descriptor.fields().iter().map(|f| f.wire_name()).collect();
+    assert_eq!(
+        names,
+        ["hostName", "network", "ports", "grid", "tags", "enabled"]
+    );
+    let d = DeviceOwned::default();
+    let base = &d as *const _ as usize;
+    let offset = |p: *const u8| p as usize - base;
+    assert_eq!(
+        find(descriptor, "host_name"),
+        (offset(d.host_name.as_ptr()), Kind::Str, 1, 8)
+    );
+    assert_eq!(
+        find(descriptor, "grid"),
+        (offset(d.grid.as_ptr() as *const u8), Kind::I16, 6, 0)
+    );
+    assert_eq!(
+        find(descriptor, "tags"),
+        (offset(d.tags.as_ptr() as *const u8), Kind::Str, 2, 4)
+    );
+    assert_eq!(
+        find(descriptor, "enabled"),
+        (
+            offset(&d.enabled as *const bool as *const u8),
+            Kind::Bool,
+            1,
+            0
+        )
+    );
+}
+
+#[test]
+fn can_describe_nested() {
+    let fields = DEVICE_DESCRIPTOR.fields();
+    assert!(fields[0].nested().is_none());
+    let network = fields[1].nested().unwrap();
+    assert!(std::ptr::eq(network, &NETWORK_DESCRIPTOR));
+    assert!(std::ptr::eq(fields[2].nested().unwrap(), network));
+    assert_eq!((fields[2].kind(), fields[2].count()), (Kind::Struct, 2));
+    assert_eq!(network.size(), std::mem::size_of::<NetworkOwned>());
+    assert_eq!(find(network, "port").1, Kind::U16);
+    assert_eq!(fields[3].dims(), [2, 3]);
+    assert_eq!(fields[3].size(), 2);
+    assert_eq!(fields[2].size(), network.size());
+    assert!(fields[0].dims().is_empty());
+}
+
+#[test]
+fn can_link_descriptor_symbols() {
+    let descriptor = unsafe { &test_descriptor_network };
+    assert!(std::ptr::eq(descriptor, &NETWORK_DESCRIPTOR));
+    let fields = unsafe { &test_descriptor_network_fields };
+    assert!(std::ptr::eq(fields, &NETWORK_FIELDS));
+    assert_eq!(descriptor.fields().len(), fields.len());
+}
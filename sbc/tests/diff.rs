@@ -0,0 +1,92 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+#[path = "common/settings.rs"]
+mod settings;
+
+use settings::*;
+
+fn diff(old: &SettingsOwned, new: &SettingsOwned) -> String {
+    let mut bytes = [0; 512];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_diff_settings(old, new, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+    std::str::from_utf8(&bytes[0..len]).unwrap().to_string()
+}
+
+#[test]
+fn can_diff_nothing() {
+    let (old, new) = (init(), init());
+    assert_eq!(test_changed_settings(&old, &new), 0);
+    assert_eq!(diff(&old, &new), "{}");
+}
+
+#[test]
+fn can_diff_changed_fields() {
+    let old = init();
+    let mut new = init();
+    new.port = 8080;
+    new.remote.id1[1] = 3;
+    new.remotes[1].id0[0] = b'x';
+    new.tags[0][0] = b'y';
+    assert_eq!(
+        test_changed_settings(&old, &new),
+        1 << 1 | 1 << 3 | 1 << 4 | 1 << 5
+    );
+    assert_eq!(
+        diff(&old, &new),
+        r#"{"port":8080,"remote":{"id1":[0,3]},"remotes":[{"id0":"","id1":[0,0]},{"id0":"x","id1":[0,0]}],"tags":["y",""]}"#
+    );
+}
+
+#[test]
+fn can_diff_ignore_bytes_after_nul() {
+    let old = init();
+    let mut new = init();
+    new.user_name[7] = b'z';
+    assert_eq!(test_changed_settings(&old, &new), 0);
+}
+
+#[test]
+fn can_merge_diff() {
+    let old = init();
+    let mut new = init();
+    new.enabled = true;
+    new.user_name[0..5].copy_from_slice(b"root\0");
+    new.remote.id0[0] = b'a';
+    let patch = diff(&old, &new);
+    let mut dst = init();
+    assert!(unsafe { test_merge_settings(&mut dst, patch.as_ptr(), patch.len()) } > 0);
+    assert_eq!(test_changed_settings(&dst, &new), 0);
+}
+
+#[test]
+fn can_not_diff_into_small_buffer() {
+    let old = init();
+    let mut new = init();
+    new.port = 8080;
+    let mut bytes = [0; 4];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_diff_settings(&old, &new, bytes.as_mut_ptr(), &mut len) },
+        -1
+    );
+}
@@ -0,0 +1,110 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::feed::{FeedState, Status};
+
+#[path = "common/foo.rs"]
+mod foo;
+
+use foo::*;
+
+fn init() -> FooOwned {
+    let mut owned = FooOwned::default();
+    test_init_foo(&mut owned);
+    owned
+}
+
+fn print(owned: &FooOwned) -> Vec<u8> {
+    let mut bytes: [u8; 512] = [0; 512];
+    let mut len = 512;
+    let ret = unsafe { test_print_foo_owned(owned, bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(ret, 0);
+    bytes[0..len].to_vec()
+}
+
+fn feed(bytes: &[u8]) -> (Status, FooOwned) {
+    let mut state = FeedState::new();
+    let mut dst = init();
+    (state.feed(&mut dst, bytes), dst)
+}
+
+#[test]
+fn can_parse_fragments_of_any_size() {
+    let expect = print(&owned());
+    for size in 1..expect.len() + 1 {
+        let mut state = FeedState::new();
+        let mut dst = init();
+        let mut chunks = expect.chunks(size).peekable();
+        while let Some(chunk) = chunks.next() {
+            let ret =
+                unsafe { test_parse_foo_feed(&mut state, &mut dst, chunk.as_ptr(), chunk.len()) };
+            if chunks.peek().is_some() {
+                assert_eq!(ret, 0);
+            } else {
+                assert_eq!(ret, chunk.len() as i32);
+            }
+        }
+        assert_eq!(print(&dst), expect);
+    }
+}
+
+#[test]
+fn can_parse_feed_and_ignore_unknown() {
+    let json = br#" { "idZero" : 7, "nope": {"a": ["}", [1, 2.5e3], null]}, "idThree":"Ab", "x": "\"" } trailing"#;
+    let (status, dst) = feed(json);
+    assert_eq!(status, Status::Complete(json.len() - " trailing".len()));
+    assert_eq!(dst.id_zero, 7);
+    assert_eq!(&dst.id_three[0..3], b"Ab\0");
+}
+
+#[test]
+fn can_not_parse_feed_unknown_nested_too_deep() {
+    let nested = |n| format!(r#"{{"nope":{}{}}}"#, "[".repeat(n), "]".repeat(n));
+    let json = nested(255);
+    assert_eq!(feed(json.as_bytes()).0, Status::Complete(json.len()));
+    assert_eq!(feed(nested(256).as_bytes()).0, Status::Error);
+}
+
+#[test]
+fn can_parse_feed_and_truncate_strings() {
+    let (status, dst) = feed(br#"{"idThree":"0123456789abcdefghij"}"#);
+    assert_eq!(status, Status::Complete(34));
+    assert_eq!(&dst.id_three, b"0123456789abcde\0");
+}
+
+#[test]
+fn can_not_parse_feed_invalid() {
+    assert_eq!(feed(br#"{"idZero":256}"#).0, Status::Error);
+    assert_eq!(feed(br#"{"idZero":"7"}"#).0, Status::Error);
+    assert_eq!(feed(br#"{"idTwo":null}"#).0, Status::Error);
+    assert_eq!(feed(br#"{"idOne":1.5}"#).0, Status::Error);
+    assert_eq!(feed(br#"{"idFour":{"id1":[1]}}"#).0, Status::Error);
+    assert_eq!(feed(br#"{"idFour":{"id1":[1,2,3]}}"#).0, Status::Error);
+    assert_eq!(feed(br#"{"idZero":1]"#).0, Status::Error);
+    assert_eq!(feed(br#"[]"#).0, Status::Error);
+    assert_eq!(feed(br#"{"idZero":1"#).0, Status::NeedMore);
+}
+
+#[test]
+fn can_not_parse_feed_after_complete() {
+    let mut state = FeedState::new();
+    let mut dst = init();
+    assert_eq!(state.feed(&mut dst, b"{}"), Status::Complete(2));
+    assert_eq!(state.feed(&mut dst, b"{}"), Status::Error);
+}
@@ -0,0 +1,94 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+
+#[derive(Debug, PartialEq)]
+#[binding(prefix = "test")]
+pub struct Item<'a> {
+    /// sbc: len = 48
+    /// sbc: default = "item"
+    pub name: &'a str,
+    /// sbc: default = 7
+    pub n: u32,
+    pub flags: [bool; 2],
+}
+
+#[derive(Debug, PartialEq)]
+#[binding(prefix = "test")]
+pub struct Big<'a> {
+    /// sbc: len = 8
+    pub name: &'a str,
+    pub items: [[[Item<'a>; 32]; 32]; 16],
+}
+
+const STACK: usize = 128 * 1024;
+
+fn on_small_stack<F: FnOnce() + Send + 'static>(f: F) {
+    std::thread::Builder::new()
+        .stack_size(STACK)
+        .spawn(f)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+fn boxed<T>() -> Box<T> {
+    unsafe { Box::new_zeroed().assume_init() }
+}
+
+#[test]
+fn can_init_in_place() {
+    let mut dst: Box<ItemOwned> = boxed();
+    test_init_item(&mut dst);
+    assert_eq!(&dst.name[..5], b"item\0");
+    assert_eq!(dst.n, 7);
+    assert_eq!(dst.flags, [false, false]);
+
+    // Owned structs much larger than the stack
+    on_small_stack(|| {
+        let mut dst: Box<BigOwned> = boxed();
+        dst.items[9][1][2].n = 1;
+        test_init_big(&mut dst);
+        assert!(dst.items.iter().flatten().flatten().all(|item| item.n == 7));
+        assert_eq!(&dst.items[15][31][31].name[..5], b"item\0");
+    });
+}
+
+#[test]
+fn can_copy_in_place() {
+    let long = "0123456789".repeat(5);
+    let src = Item {
+        name: &long,
+        n: 3,
+        flags: [true, false],
+    };
+    let mut dst: Box<ItemOwned> = boxed();
+    dst.name = [0xff; 48];
+    test_copy_item(&mut dst, &src);
+    let expect = ItemOwned::from(&src);
+    assert_eq!((dst.n, dst.flags), (expect.n, expect.flags));
+    assert_eq!(&dst.name[..47], &long.as_bytes()[..47]);
+    assert_eq!(dst.name[47], 0);
+
+    let src = Item { name: "ab", ..src };
+    test_copy_item(&mut dst, &src);
+    assert_eq!(&dst.name[..], &ItemOwned::from(&src).name[..]);
+    assert!(dst.name[2..].iter().all(|b| *b == 0));
+}
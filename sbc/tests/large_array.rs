@@ -0,0 +1,78 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 8
+    /// sbc: default = "r"
+    pub id0: &'a str,
+}
+
+#[binding(prefix = "test")]
+pub struct Large<'a> {
+    /// sbc: len = 8
+    pub name: &'a str,
+    pub bytes: [u8; 32],
+    pub grid: [[u16; 20]; 3],
+    /// sbc: len = 4
+    /// sbc: default = "t"
+    pub tags: [&'a str; 17],
+    pub remotes: [Remote<'a>; 18],
+}
+
+#[binding(prefix = "test", mode = "table")]
+pub struct Huge {
+    pub bytes: [u8; 1024],
+}
+
+#[test]
+fn can_default_large_arrays() {
+    let large = LargeOwned::default();
+    assert_eq!(large.bytes, [0; 32]);
+    assert!(large.tags.iter().all(|t| t == b"t\0\0\0"));
+    assert!(large.remotes.iter().all(|r| &r.id0[0..2] == b"r\0"));
+    assert_eq!(HugeOwned::default().bytes, [0; 1024]);
+}
+
+#[test]
+fn can_copy_large_arrays() {
+    let mut grid = [[0u16; 20]; 3];
+    grid[2][19] = 7;
+    let names: Vec<String> = (0..18).map(|i| format!("r{}", i)).collect();
+    let remotes: [Remote; 18] = core::array::from_fn(|i| Remote { id0: &names[i] });
+    let large = Large {
+        name: "large",
+        bytes: core::array::from_fn(|i| i as u8),
+        grid,
+        tags: core::array::from_fn(|i| if i == 16 { "last" } else { "" }),
+        remotes,
+    };
+    let owned = LargeOwned::from(&large);
+    assert_eq!(owned.bytes[31], 31);
+    assert_eq!(owned.grid[2][19], 7);
+    assert_eq!(&owned.tags[16], b"las\0");
+    assert_eq!(&owned.remotes[17].id0[0..4], b"r17\0");
+
+    let borrowed = LargeBorrowed::from(&owned);
+    assert_eq!(borrowed.tags[16], "las");
+    assert_eq!(borrowed.remotes[17].id0, "r17");
+    assert_eq!(borrowed.grid[2][19], 7);
+}
@@ -0,0 +1,75 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+use serde_bindgen_core::layout::Layout;
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 3
+    pub id0: &'a str,
+    pub id1: u32,
+}
+
+#[binding(prefix = "test", mode = "table")]
+pub struct Device<'a> {
+    pub enabled: bool,
+    pub remote: Remote<'a>,
+    pub level: i16,
+    pub remotes: [Remote<'a>; 2],
+    /// sbc: len = 5
+    pub tags: [&'a str; 2],
+}
+
+// Remote with one field renamed
+#[binding(prefix = "test")]
+pub struct Other<'a> {
+    /// sbc: len = 3
+    pub id0: &'a str,
+    pub id2: u32,
+}
+
+#[test]
+fn can_lay_out_like_c() {
+    let layout = Layout::new([(1, 1), (4, 4), (2, 2), (3, 1)]);
+    assert_eq!(layout.offsets, [0, 4, 8, 10]);
+    assert_eq!(layout.size, 16);
+    assert_eq!(layout.align, 4);
+    assert_eq!(Layout::new([]).size, 0);
+}
+
+#[test]
+fn can_export_layout() {
+    assert_eq!(REMOTE_LAYOUT.offsets, [0, 4]);
+    assert_eq!(REMOTE_LAYOUT.size, 8);
+    assert_eq!(DEVICE_LAYOUT.offsets, [0, 4, 12, 16, 32]);
+    assert_eq!(DEVICE_LAYOUT.size, 44);
+    assert_eq!(DEVICE_LAYOUT.align, 4);
+    assert_eq!(core::mem::size_of::<DeviceOwned>(), DEVICE_LAYOUT.size);
+}
+
+#[test]
+fn can_hash_abi() {
+    // The values sbc-gen writes to the header for these structs
+    assert_eq!(REMOTE_ABI_HASH, 0x855fbc2e4a2e3956);
+    assert_eq!(DEVICE_ABI_HASH, 0x3e0ffdccd7587ae6);
+    assert_eq!(test_abi_hash_device(), DEVICE_ABI_HASH);
+    assert_eq!(REMOTE_LAYOUT, OTHER_LAYOUT);
+    assert_ne!(REMOTE_ABI_HASH, OTHER_ABI_HASH);
+}
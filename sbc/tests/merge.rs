@@ -0,0 +1,65 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+#[path = "common/settings.rs"]
+mod settings;
+
+use settings::*;
+
+fn merge(dst: &mut SettingsOwned, json: &str) -> i32 {
+    unsafe { test_merge_settings(dst, json.as_ptr(), json.len()) }
+}
+
+#[test]
+fn can_merge_present_keys() {
+    let mut dst = init();
+    let json = r#"{"port":8080,"remote":{"id1":[1,2]}}"#;
+    assert_eq!(merge(&mut dst, json), json.len() as i32);
+    assert_eq!(&dst.user_name, b"admin\0\0\0");
+    assert_eq!(dst.port, 8080);
+    assert!(!dst.enabled);
+    assert_eq!(&dst.remote.id0, b"\0\0\0\0\0\0\0\0");
+    assert_eq!(dst.remote.id1, [1, 2]);
+
+    let json = r#"{"userName":"root","enabled":true,"remote":{"id0":"a"}}"#;
+    assert_eq!(merge(&mut dst, json), json.len() as i32);
+    assert_eq!(&dst.user_name, b"root\0\0\0\0");
+    assert_eq!(dst.port, 8080);
+    assert!(dst.enabled);
+    assert_eq!(&dst.remote.id0, b"a\0\0\0\0\0\0\0");
+    assert_eq!(dst.remote.id1, [1, 2]);
+}
+
+#[test]
+fn can_merge_arrays_whole() {
+    let mut dst = init();
+    let json = r#"{"remotes":[{"id0":"b","id1":[3,4]},{"id0":"c","id1":[5,6]}]}"#;
+    assert_eq!(merge(&mut dst, json), json.len() as i32);
+    assert_eq!(&dst.remotes[0].id0, b"b\0\0\0\0\0\0\0");
+    assert_eq!(dst.remotes[1].id1, [5, 6]);
+    assert_eq!(dst.port, 80);
+}
+
+#[test]
+fn can_not_merge_invalid() {
+    let mut dst = init();
+    assert_eq!(merge(&mut dst, r#"{"port":"80"}"#), -1);
+    assert_eq!(merge(&mut dst, r#"{"remotes":[{"id0":"b"}]}"#), -1);
+    assert_eq!(dst.port, 80);
+}
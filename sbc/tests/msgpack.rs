@@ -0,0 +1,197 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::msgpack::{self, Error};
+
+#[path = "common/device.rs"]
+mod device;
+
+use device::*;
+
+fn remote_bytes(level: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0x82, 0xa6];
+    bytes.extend(b"hostId");
+    bytes.extend([0xa2, b'a', b'b', 0xa5]);
+    bytes.extend(b"level");
+    bytes.extend(level);
+    bytes
+}
+
+#[test]
+fn can_print_msgpack() {
+    let mut bytes = [0; REMOTE_MSGPACK_MAX_LEN];
+    let mut len = bytes.len();
+    let rc = unsafe { test_msgpack_print_remote(&remote("ab", -2), bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(rc, 0);
+    assert_eq!(bytes[..len], remote_bytes(&[0xfe]));
+
+    // the smallest form of a number
+    let expect: [(i16, &[u8]); 6] = [
+        (127, &[0x7f]),
+        (128, &[0xcc, 0x80]),
+        (256, &[0xcd, 0x01, 0x00]),
+        (-32, &[0xe0]),
+        (-33, &[0xd0, 0xdf]),
+        (-129, &[0xd1, 0xff, 0x7f]),
+    ];
+    for (level, number) in expect {
+        let len = msgpack::to_slice(&remote("ab", level), &mut bytes).unwrap();
+        assert_eq!(bytes[..len], remote_bytes(number));
+    }
+
+    let mut small = [0; 4];
+    let err = msgpack::to_slice(&remote("ab", -2), &mut small);
+    assert_eq!(err, Err(Error::BufferFull));
+}
+
+#[test]
+fn can_bound_msgpack_len() {
+    assert_eq!(REMOTE_MSGPACK_MAX_LEN, 21);
+    assert_eq!(DEVICE_MSGPACK_MAX_LEN, 127);
+    let device = DeviceOwned {
+        name: *b"abcd\0",
+        enabled: true,
+        remote: remote("abc", i16::MIN),
+        grid: [[255; 2]; 2],
+        remotes: [remote("abc", i16::MIN), remote("abc", i16::MAX)],
+        tags: [*b"ab\0", *b"ab\0"],
+    };
+    let mut bytes = [0; DEVICE_MSGPACK_MAX_LEN];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_msgpack_print_device(&device, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+    assert_eq!(len, DEVICE_MSGPACK_MAX_LEN);
+}
+
+#[test]
+fn can_round_trip_msgpack() {
+    let mut device = DeviceOwned::default();
+    device.enabled = true;
+    device.remote = remote("abc", -300);
+    device.grid = [[1, 2], [3, 255]];
+    device.remotes[1] = remote("x", 7);
+    device.tags[0] = *b"hi\0";
+    let mut bytes = [0; DEVICE_MSGPACK_MAX_LEN];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_msgpack_print_device(&device, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+
+    let mut decoded = DeviceOwned::default();
+    let rc = unsafe { test_msgpack_parse_device(&mut decoded, bytes.as_ptr(), len) };
+    assert_eq!(rc, len as i32);
+    assert!(decoded.enabled);
+    assert_eq!(decoded.remote.host_id, *b"abc\0");
+    assert_eq!(decoded.remote.level, -300);
+    assert_eq!(decoded.grid, [[1, 2], [3, 255]]);
+    assert_eq!(decoded.remotes[1].host_id, *b"x\0\0\0");
+    assert_eq!(decoded.remotes[1].level, 7);
+    assert_eq!(decoded.tags, [*b"hi\0", [0; 3]]);
+
+    // truncated, or followed by more
+    assert_eq!(
+        unsafe { test_msgpack_parse_device(&mut decoded, bytes.as_ptr(), len - 1) },
+        -1
+    );
+    assert_eq!(
+        unsafe { test_msgpack_parse_device(&mut decoded, bytes.as_ptr(), len + 1) },
+        -1
+    );
+}
+
+#[test]
+fn can_parse_msgpack_in_place() {
+    let device = DeviceOwned {
+        enabled: true,
+        remote: remote("abc", -300),
+        ..Default::default()
+    };
+    let mut bytes = [0; DEVICE_MSGPACK_MAX_LEN];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_msgpack_print_device(&device, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+
+    // dst is written as the message is read, so a truncated one leaves the
+    // fields before the end
+    let mut decoded = DeviceOwned::default();
+    assert_eq!(
+        unsafe { test_msgpack_parse_device(&mut decoded, bytes.as_ptr(), len - 1) },
+        -1
+    );
+    assert!(decoded.enabled);
+    assert_eq!(decoded.remote.level, -300);
+}
+
+#[test]
+fn can_parse_msgpack_from_others() {
+    // a wider number than needed, and an unknown key
+    let mut bytes = vec![0x83, 0xa6];
+    bytes.extend(b"hostId");
+    bytes.extend([0xd9, 2, b'a', b'b', 0xa5]);
+    bytes.extend(b"level");
+    bytes.extend([0xd3, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]);
+    bytes.extend([0xa1, b'x', 0x92, 0xc0, 0x81, 0x01, 0xc3]);
+    let (remote, len) = msgpack::from_slice::<RemoteOwned>(&bytes).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(remote.host_id, *b"ab\0\0");
+    assert_eq!(remote.level, -2);
+
+    // a struct as an array of its fields
+    let bytes = [0x92, 0xa1, b'a', 0x05];
+    let (remote, _) = msgpack::from_slice::<RemoteOwned>(&bytes).unwrap();
+    assert_eq!(remote.level, 5);
+}
+
+#[test]
+fn can_reject_msgpack() {
+    // out of range
+    let bytes = remote_bytes(&[0xcd, 0x80, 0x00]);
+    assert!(msgpack::from_slice::<RemoteOwned>(&bytes).is_err());
+    // too many elements
+    let bytes = [0x93, 0xa1, b'a', 0x05, 0x05];
+    assert_eq!(
+        msgpack::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Invalid)
+    );
+    // not utf-8
+    let bytes = [0x92, 0xa1, 0xff, 0x05];
+    assert_eq!(
+        msgpack::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Invalid)
+    );
+    // nested too deep, under an unknown key
+    let mut bytes = vec![0x81, 0xa1, b'x'];
+    bytes.extend([0x91; 40]);
+    bytes.push(0x05);
+    assert_eq!(
+        msgpack::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Invalid)
+    );
+    // truncated
+    let bytes = [0x92, 0xa4, b'a'];
+    assert_eq!(
+        msgpack::from_slice::<RemoteOwned>(&bytes).map(|_| ()),
+        Err(Error::Eof)
+    );
+}
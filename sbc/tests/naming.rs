@@ -0,0 +1,62 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+
+#[binding(
+    prefix = "acme",
+    symbol = "{prefix}_{type}_{verb}",
+    owned = "acme_foo_t",
+    borrowed = "acme_foo_ref_t"
+)]
+pub struct Foo<'a> {
+    /// sbc: len = 8
+    /// sbc: default = "foo"
+    pub name: &'a str,
+    pub n: u8,
+}
+
+#[test]
+fn can_name_symbols() {
+    let mut owned: acme_foo_t = Default::default();
+    owned.n = 9;
+    acme_foo_init(&mut owned);
+    assert_eq!(owned.n, 0);
+    assert_eq!(&owned.name[..4], b"foo\0");
+
+    let json = r#"{"name":"bar","n":3}"#;
+    let mut dst: acme_foo_ref_t = Foo { name: "", n: 0 };
+    assert_eq!(
+        unsafe { acme_foo_parse(&mut dst, json.as_ptr(), json.len()) },
+        json.len() as i32
+    );
+    acme_foo_copy(&mut owned, &dst);
+    assert_eq!(&owned.name[..4], b"bar\0");
+
+    let mut bytes = [0u8; FOO_MAX_LEN];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { acme_acme_foo_t_print(&owned, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+    assert_eq!(&bytes[..len], json.as_bytes());
+    let mut len = bytes.len();
+    unsafe { acme_acme_foo_ref_t_print(&dst, bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(&bytes[..len], json.as_bytes());
+}
@@ -0,0 +1,85 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 6
+    pub id0: &'a str,
+}
+
+#[binding(prefix = "test", rename_all = "camelCase")]
+pub struct Foo<'a> {
+    pub id_zero: u8,
+    /// sbc: len = 6
+    pub id_one: &'a str,
+    pub id_two: Remote<'a>,
+    /// sbc: len = 4
+    pub id_three: [[&'a str; 2]; 2],
+    pub id_four: [u8; 3],
+}
+
+const DATA: &'static str = r#"{"idZero":1,"idOne":"hello","idTwo":{"id0":"a"},"idThree":[["b","c"],["d","e"]],"idFour":[2,3,4]}"#;
+
+#[test]
+fn can_serialize_owned() {
+    let mut owned = FooOwned::default();
+    test_init_foo(&mut owned);
+    owned.id_zero = 1;
+    owned.id_one = *b"hello\0";
+    owned.id_two.id0 = *b"a\0xxxx";
+    owned.id_three = [[*b"b\0\0\0", *b"c\0\0\0"], [*b"d\0\0\0", *b"e\0\0\0"]];
+    owned.id_four = [2, 3, 4];
+
+    let mut bytes: [u8; 256] = [0; 256];
+    let len = serde_json_core::to_slice(&owned, &mut bytes).unwrap();
+    assert_eq!(std::str::from_utf8(&bytes[0..len]).unwrap(), DATA);
+}
+
+#[test]
+fn can_deserialize_owned() {
+    let (owned, len): (FooOwned, usize) = serde_json_core::from_str(DATA).unwrap();
+    assert_eq!(len, DATA.len());
+    assert_eq!(owned.id_zero, 1);
+    assert_eq!(&owned.id_one, b"hello\0");
+    assert_eq!(&owned.id_two.id0, b"a\0\0\0\0\0");
+    assert_eq!(&owned.id_three[1][0], b"d\0\0\0");
+    assert_eq!(owned.id_four, [2, 3, 4]);
+}
+
+#[test]
+fn can_deserialize_owned_truncated() {
+    let data = r#"{"idZero":1,"idOne":"too long","idTwo":{"id0":"a"},"idThree":[["b","c"],["d","e"]],"idFour":[2,3,4]}"#;
+    let (owned, _): (FooOwned, usize) = serde_json_core::from_str(data).unwrap();
+    assert_eq!(&owned.id_one, b"too l\0");
+}
+
+#[test]
+fn can_print_owned_without_nul() {
+    // A string which fills the whole buffer has no terminator
+    let (mut owned, _): (FooOwned, usize) = serde_json_core::from_str(DATA).unwrap();
+    owned.id_one = *b"abcdef";
+    let mut bytes: [u8; 256] = [0; 256];
+    let mut len = 256;
+    let ret = unsafe { test_print_foo_owned(&owned, bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(ret, 0);
+    let (parsed, _): (Foo, usize) = serde_json_core::from_slice(&bytes[0..len]).unwrap();
+    assert_eq!(parsed.id_one, "abcdef");
+}
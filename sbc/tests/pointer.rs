@@ -0,0 +1,141 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+
+#[binding(prefix = "test")]
+pub struct Network<'a> {
+    /// sbc: len = 16
+    /// sbc: default = "192.168.0.1"
+    pub ip: &'a str,
+    /// sbc: default = 80
+    pub port: u16,
+}
+
+#[binding(prefix = "test", rename_all = "kebab-case")]
+pub struct Device<'a> {
+    /// sbc: len = 8
+    pub host_name: &'a str,
+    pub network: Network<'a>,
+    pub ports: [Network<'a>; 2],
+    pub grid: [[u8; 2]; 2],
+    /// sbc: len = 4
+    pub tags: [&'a str; 2],
+    pub enabled: bool,
+}
+
+fn init() -> DeviceOwned {
+    let mut owned = DeviceOwned::default();
+    test_init_device(&mut owned);
+    owned
+}
+
+fn get(data: &DeviceOwned, pointer: &str) -> Option<String> {
+    let mut bytes = [0; 512];
+    let mut len = bytes.len();
+    let ret = unsafe {
+        test_get_device(
+            data,
+            pointer.as_ptr(),
+            pointer.len(),
+            bytes.as_mut_ptr(),
+            &mut len,
+        )
+    };
+    match ret {
+        0 => Some(std::str::from_utf8(&bytes[0..len]).unwrap().to_string()),
+        _ => None,
+    }
+}
+
+fn set(dst: &mut DeviceOwned, pointer: &str, value: &str) -> i32 {
+    unsafe {
+        test_set_device(
+            dst,
+            pointer.as_ptr(),
+            pointer.len(),
+            value.as_ptr(),
+            value.len(),
+        )
+    }
+}
+
+#[test]
+fn can_get_by_pointer() {
+    let data = init();
+    assert_eq!(get(&data, "/network/ip").unwrap(), r#""192.168.0.1""#);
+    assert_eq!(get(&data, "/network/port").unwrap(), "80");
+    assert_eq!(
+        get(&data, "/network").unwrap(),
+        r#"{"ip":"192.168.0.1","port":80}"#
+    );
+    assert_eq!(get(&data, "/ports/1/port").unwrap(), "80");
+    assert_eq!(get(&data, "/grid").unwrap(), "[[0,0],[0,0]]");
+    assert_eq!(get(&data, "/grid/1/0").unwrap(), "0");
+    assert_eq!(get(&data, "/tags").unwrap(), r#"["",""]"#);
+    assert_eq!(get(&data, "/host-name").unwrap(), r#""""#);
+    assert!(get(&data, "").unwrap().starts_with(r#"{"host-name":"""#));
+}
+
+#[test]
+fn can_not_get_by_invalid_pointer() {
+    let data = init();
+    assert_eq!(get(&data, "/nope"), None);
+    assert_eq!(get(&data, "/host_name"), None);
+    assert_eq!(get(&data, "network"), None);
+    assert_eq!(get(&data, "/network/ip/0"), None);
+    assert_eq!(get(&data, "/ports/2"), None);
+    assert_eq!(get(&data, "/ports/01"), None);
+    assert_eq!(get(&data, "/ports/-"), None);
+    assert_eq!(get(&data, "/enabled/"), None);
+}
+
+#[test]
+fn can_set_by_pointer() {
+    let mut data = init();
+    assert_eq!(set(&mut data, "/network/ip", r#""10.0.0.1""#), 0);
+    assert_eq!(set(&mut data, "/network/port", " 8080 "), 0);
+    assert_eq!(set(&mut data, "/ports/1", r#"{"port":1}"#), 0);
+    assert_eq!(set(&mut data, "/grid/1", "[3, 4]"), 0);
+    assert_eq!(set(&mut data, "/tags/0", r#""abcdef""#), 0);
+    assert_eq!(set(&mut data, "/enabled", "true"), 0);
+    assert_eq!(
+        get(&data, "/network").unwrap(),
+        r#"{"ip":"10.0.0.1","port":8080}"#
+    );
+    assert_eq!(
+        get(&data, "/ports/1").unwrap(),
+        r#"{"ip":"192.168.0.1","port":1}"#
+    );
+    assert_eq!(get(&data, "/grid").unwrap(), "[[0,0],[3,4]]");
+    assert_eq!(get(&data, "/tags").unwrap(), r#"["abc",""]"#);
+    assert_eq!(get(&data, "/enabled").unwrap(), "true");
+}
+
+#[test]
+fn can_not_set_invalid() {
+    let mut data = init();
+    assert_eq!(set(&mut data, "/network/port", "70000"), -1);
+    assert_eq!(set(&mut data, "/network/port", "80 81"), -1);
+    assert_eq!(set(&mut data, "/network/port", ""), -1);
+    assert_eq!(set(&mut data, "/network/ip", "1"), -1);
+    assert_eq!(set(&mut data, "/grid/1", "[3]"), -1);
+    assert_eq!(set(&mut data, "/nope", "1"), -1);
+    assert_eq!(get(&data, "/network/port").unwrap(), "80");
+}
@@ -0,0 +1,165 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+use serde_bindgen_core::protobuf::{self, Error};
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 4
+    pub id: &'a str,
+    /// sbc: tag = 5
+    pub level: i16,
+}
+
+#[binding(prefix = "test", mode = "table")]
+pub struct Device<'a> {
+    pub enabled: bool,
+    pub remote: Remote<'a>,
+    pub grid: [[u8; 2]; 2],
+    pub remotes: [Remote<'a>; 2],
+    /// sbc: len = 3
+    pub tags: [&'a str; 2],
+}
+
+#[binding(prefix = "test", only = ["pb_decode"])]
+pub struct Limits {
+    /// sbc: default = 8
+    pub retries: u8,
+    pub level: i16,
+}
+
+fn remote(id: &[u8], level: i16) -> RemoteOwned {
+    let mut owned = RemoteOwned::default();
+    owned.id[..id.len()].copy_from_slice(id);
+    owned.level = level;
+    owned
+}
+
+#[test]
+fn can_encode_protobuf() {
+    let mut bytes = [0; REMOTE_PB_MAX_LEN];
+    let len = protobuf::encode(&remote(b"ab", -2), &mut bytes).unwrap();
+    assert_eq!(bytes[..len], [0x0a, 2, b'a', b'b', 0x28, 3]);
+
+    // zero numbers and empty strings are skipped, array elements are not
+    let mut bytes = [0; DEVICE_PB_MAX_LEN];
+    let len = protobuf::encode(&DeviceOwned::default(), &mut bytes).unwrap();
+    let expect = [
+        0x12, 0, 0x1a, 4, 0, 0, 0, 0, 0x22, 0, 0x22, 0, 0x2a, 0, 0x2a, 0,
+    ];
+    assert_eq!(bytes[..len], expect);
+    assert_eq!(protobuf::encoded_len(&DeviceOwned::default()), Ok(len));
+    let mut small = [0; 4];
+    let err = protobuf::encode(&DeviceOwned::default(), &mut small);
+    assert_eq!(err, Err(Error::BufferFull));
+}
+
+#[test]
+fn can_bound_protobuf_len() {
+    assert_eq!(REMOTE_PB_MAX_LEN, 9);
+    assert_eq!(DEVICE_PB_MAX_LEN, 53);
+    let device = DeviceOwned {
+        enabled: true,
+        remote: remote(b"abc", i16::MIN),
+        grid: [[255; 2]; 2],
+        remotes: [remote(b"abc", i16::MAX), remote(b"abc", i16::MIN)],
+        tags: [*b"ab\0", *b"ab\0"],
+    };
+    assert_eq!(protobuf::encoded_len(&device), Ok(DEVICE_PB_MAX_LEN));
+}
+
+#[test]
+fn can_round_trip_protobuf() {
+    let device = DeviceOwned {
+        enabled: true,
+        remote: remote(b"abc", -300),
+        grid: [[1, 2], [3, 255]],
+        remotes: [RemoteOwned::default(), remote(b"x", 7)],
+        tags: [*b"hi\0", [0; 3]],
+    };
+    let mut bytes = [0; DEVICE_PB_MAX_LEN];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_pb_encode_device(&device, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+
+    let mut decoded = DeviceOwned::default();
+    decoded.tags[1] = *b"no\0";
+    let rc = unsafe { test_pb_decode_device(&mut decoded, bytes.as_ptr(), len) };
+    assert_eq!(rc, 0);
+    assert!(decoded.enabled);
+    assert_eq!(decoded.remote.id, *b"abc\0");
+    assert_eq!(decoded.remote.level, -300);
+    assert_eq!(decoded.grid, [[1, 2], [3, 255]]);
+    assert_eq!(decoded.remotes[0].id, [0; 4]);
+    assert_eq!(decoded.remotes[1].level, 7);
+    assert_eq!(decoded.tags, [*b"hi\0", [0; 3]]);
+}
+
+#[test]
+fn can_decode_protobuf_from_others() {
+    // unpacked numbers, packed in two pieces, and unknown fields
+    let bytes = [
+        0x18, 7, 0x1a, 2, 8, 9, 0x18, 10, 0x30, 1, 0x3a, 1, 0, 0x41, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    let mut device = DeviceOwned::default();
+    assert_eq!(protobuf::decode(&mut device, &bytes), Ok(()));
+    assert_eq!(device.grid, [[7, 8], [9, 10]]);
+}
+
+#[test]
+fn can_reject_protobuf() {
+    let mut device = DeviceOwned::default();
+    // too many elements
+    let bytes = [0x1a, 5, 1, 2, 3, 4, 5];
+    assert_eq!(protobuf::decode(&mut device, &bytes), Err(Error::Invalid));
+    // no room for the NUL
+    let bytes = [0x2a, 3, b'a', b'b', b'c'];
+    assert_eq!(protobuf::decode(&mut device, &bytes), Err(Error::Invalid));
+    // out of range
+    let bytes = [0x1a, 2, 0x80, 2];
+    assert_eq!(protobuf::decode(&mut device, &bytes), Err(Error::Invalid));
+    // wrong wire type
+    let bytes = [0x0a, 0];
+    assert_eq!(protobuf::decode(&mut device, &bytes), Err(Error::Invalid));
+    // truncated
+    let bytes = [0x12, 4, 0x0a];
+    assert_eq!(protobuf::decode(&mut device, &bytes), Err(Error::Eof));
+}
+
+#[test]
+fn can_decode_protobuf_binding_to_defaults() {
+    // absent fields take their default, not zero
+    let mut limits = LimitsOwned {
+        retries: 0,
+        level: 9,
+    };
+    let bytes = [0x10, 4];
+    let rc = unsafe { test_pb_decode_limits(&mut limits, bytes.as_ptr(), bytes.len()) };
+    assert_eq!(rc, 0);
+    assert_eq!((limits.retries, limits.level), (8, 2));
+
+    // a truncated message leaves dst alone
+    let bytes = [0x08, 3, 0x10];
+    let rc = unsafe { test_pb_decode_limits(&mut limits, bytes.as_ptr(), bytes.len()) };
+    assert_eq!(rc, -1);
+    assert_eq!((limits.retries, limits.level), (8, 2));
+}
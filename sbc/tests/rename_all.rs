@@ -1,6 +1,4 @@
-use serde;
 use serde_bindgen_core::binding;
-use serde_json_core;
 
 #[binding(prefix = "test", rename_all = "camelCase")]
 pub struct Foo {
@@ -0,0 +1,63 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+
+#[binding(prefix = "test", skip = ["print_owned", "copy"])]
+pub struct Telemetry<'a> {
+    /// sbc: len = 8
+    pub id: &'a str,
+    pub value: u32,
+}
+
+#[binding(prefix = "test", only = ["parse"])]
+pub struct Boot<'a> {
+    /// sbc: len = 8
+    pub image: &'a str,
+}
+
+// These clash with the generated symbols unless the bindings were skipped
+#[no_mangle]
+pub extern "C" fn test_print_telemetry_owned() {}
+#[no_mangle]
+pub extern "C" fn test_copy_telemetry() {}
+#[no_mangle]
+pub extern "C" fn test_init_boot() {}
+#[no_mangle]
+pub extern "C" fn test_print_boot_borrowed() {}
+
+#[test]
+fn can_skip_bindings() {
+    let src = Telemetry { id: "a", value: 3 };
+    let mut bytes = [0u8; 64];
+    let mut len = bytes.len();
+    unsafe { test_print_telemetry_borrowed(&src, bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(&bytes[..len], br#"{"id":"a","value":3}"#);
+}
+
+#[test]
+fn can_only_bindings() {
+    let json = r#"{"image":"boot"}"#;
+    let mut dst = Boot { image: "" };
+    assert_eq!(
+        unsafe { test_parse_boot(&mut dst, json.as_ptr(), json.len()) },
+        json.len() as i32
+    );
+    assert_eq!(dst.image, "boot");
+}
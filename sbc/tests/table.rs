@@ -0,0 +1,140 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 8
+    /// sbc: default = "r"
+    pub id0: &'a str,
+    pub id1: [i32; 2],
+}
+
+#[binding(prefix = "test", rename_all = "camelCase")]
+pub struct Device<'a> {
+    /// sbc: len = 8
+    /// sbc: default = "host"
+    pub host_name: &'a str,
+    /// sbc: default = -3
+    pub level: i8,
+    pub enabled: bool,
+    pub remote: Remote<'a>,
+    pub remotes: [Remote<'a>; 2],
+    pub grid: [[u16; 3]; 2],
+    /// sbc: len = 4
+    pub tags: [&'a str; 2],
+}
+
+#[binding(prefix = "test", mode = "table")]
+pub struct RemoteTable<'a> {
+    /// sbc: len = 8
+    /// sbc: default = "r"
+    pub id0: &'a str,
+    pub id1: [i32; 2],
+}
+
+#[binding(prefix = "test", rename_all = "camelCase", mode = "table")]
+pub struct DeviceTable<'a> {
+    /// sbc: len = 8
+    /// sbc: default = "host"
+    pub host_name: &'a str,
+    /// sbc: default = -3
+    pub level: i8,
+    pub enabled: bool,
+    pub remote: RemoteTable<'a>,
+    pub remotes: [RemoteTable<'a>; 2],
+    pub grid: [[u16; 3]; 2],
+    /// sbc: len = 4
+    pub tags: [&'a str; 2],
+}
+
+const JSON: &str = r#"{"hostName":"abc","level":-128,"enabled":true,"remote":{"id0":"x","id1":[1,-2]},"remotes":[{"id0":"y","id1":[3,4]},{"id0":"z","id1":[5,6]}],"grid":[[1,2,3],[4,5,65535]],"tags":["a","bcd"]}"#;
+
+fn print_serde(data: &DeviceOwned) -> String {
+    let mut bytes = [0; 512];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_print_device_owned(data, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+    std::str::from_utf8(&bytes[0..len]).unwrap().to_string()
+}
+
+fn print_table(data: &DeviceTableOwned) -> String {
+    let mut bytes = [0; 512];
+    let mut len = bytes.len();
+    assert_eq!(
+        unsafe { test_print_device_table_owned(data, bytes.as_mut_ptr(), &mut len) },
+        0
+    );
+    std::str::from_utf8(&bytes[0..len]).unwrap().to_string()
+}
+
+#[test]
+fn can_print_table_like_serde() {
+    let serde = DeviceOwned::default();
+    let table = DeviceTableOwned::default();
+    assert_eq!(print_table(&table), print_serde(&serde));
+}
+
+#[test]
+fn can_parse_table() {
+    let mut table = DeviceTableOwned::default();
+    let ret = unsafe { test_parse_device_table_owned(&mut table, JSON.as_ptr(), JSON.len()) };
+    assert_eq!(ret, JSON.len() as i32);
+    assert_eq!(print_table(&table), JSON);
+    assert_eq!(table.grid[1][2], 65535);
+    assert_eq!(&table.remotes[1].id0[0..2], b"z\0");
+}
+
+#[test]
+fn can_parse_table_partially() {
+    let mut table = DeviceTableOwned::default();
+    let json = r#"{"level":7,"remotes":[{"id1":[1,2]},{}],"nope":[{}]}"#;
+    let ret = unsafe { test_parse_device_table_owned(&mut table, json.as_ptr(), json.len()) };
+    assert_eq!(ret, json.len() as i32);
+    assert_eq!(table.level, 7);
+    assert_eq!(&table.host_name[0..5], b"host\0");
+    assert_eq!(table.remotes[0].id1, [1, 2]);
+    assert_eq!(&table.remotes[0].id0[0..2], b"r\0");
+}
+
+#[test]
+fn can_not_parse_table_invalid() {
+    let mut table = DeviceTableOwned::default();
+    for json in [
+        r#"{"level":128}"#,
+        r#"{"grid":[[1,2,3]]}"#,
+        r#"{"remote":[]}"#,
+        r#"{"level":1"#,
+    ] {
+        let ret = unsafe { test_parse_device_table_owned(&mut table, json.as_ptr(), json.len()) };
+        assert_eq!(ret, -1);
+    }
+}
+
+#[test]
+fn can_not_print_table_into_small_buffer() {
+    let table = DeviceTableOwned::default();
+    let mut bytes = [0; 8];
+    let mut len = bytes.len();
+    let ret = unsafe { test_print_device_table_owned(&table, bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(ret, -1);
+}
@@ -0,0 +1,121 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+use serde_bindgen_core::{FromView, StrView};
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 8
+    pub id0: &'a str,
+}
+
+#[binding(prefix = "test")]
+pub struct Foo<'a> {
+    pub id0: u8,
+    /// sbc: len = 8
+    pub id1: &'a str,
+    pub id2: Remote<'a>,
+    pub id3: [Remote<'a>; 2],
+    /// sbc: len = 8
+    pub id4: [&'a str; 2],
+}
+
+#[test]
+fn can_convert_to_view() {
+    let foo = Foo {
+        id0: 3,
+        id1: "hello",
+        id2: Remote { id0: "a" },
+        id3: [Remote { id0: "b" }, Remote { id0: "c" }],
+        id4: ["d", "e"],
+    };
+    let view: FooView = (&foo).into();
+    assert_eq!(view.id0, 3);
+    assert_eq!(view.id1.len, 5);
+    assert_eq!(unsafe { view.id1.as_str() }.unwrap(), "hello");
+    assert_eq!(unsafe { view.id2.id0.as_str() }.unwrap(), "a");
+    assert_eq!(unsafe { view.id3[1].id0.as_str() }.unwrap(), "c");
+    assert_eq!(unsafe { view.id4[0].as_str() }.unwrap(), "d");
+
+    let borrowed = unsafe { Foo::from_view(&view) }.unwrap();
+    assert_eq!(borrowed.id1, "hello");
+    assert_eq!(borrowed.id3[0].id0, "b");
+    assert_eq!(borrowed.id4[1], "e");
+}
+
+#[test]
+fn can_print_view() {
+    // Strings longer than the owned capacity are printed in full from a view
+    let name = "this is longer than 8";
+    let view = FooView {
+        id0: 7,
+        id1: StrView::from(name),
+        id2: RemoteView {
+            id0: StrView::from("a"),
+        },
+        id3: [
+            RemoteView {
+                id0: StrView::from("b"),
+            },
+            RemoteView {
+                id0: StrView {
+                    ptr: core::ptr::null(),
+                    len: 0,
+                },
+            },
+        ],
+        id4: [StrView::from("d"), StrView::from("e")],
+    };
+    let mut bytes: [u8; 256] = [0; 256];
+    let mut l = 256;
+    let ret = unsafe { test_print_foo_view(&view, bytes.as_mut_ptr(), &mut l) };
+    assert_eq!(ret, 0);
+    let expect = r#"{"id0":7,"id1":"this is longer than 8","id2":{"id0":"a"},"id3":[{"id0":"b"},{"id0":""}],"id4":["d","e"]}"#;
+    assert_eq!(std::str::from_utf8(&bytes[0..l]).unwrap(), expect);
+}
+
+#[test]
+fn can_not_convert_view_invalid_utf8() {
+    let bytes = [b'a', 0xff];
+    let invalid = StrView {
+        ptr: bytes.as_ptr(),
+        len: bytes.len(),
+    };
+    assert!(unsafe { invalid.as_str() }.is_err());
+
+    let remote = |id0| RemoteView { id0 };
+    let mut view = FooView {
+        id0: 7,
+        id1: StrView::from("a"),
+        id2: remote(StrView::from("b")),
+        id3: [remote(StrView::from("c")), remote(invalid)],
+        id4: [StrView::from("d"), StrView::from("e")],
+    };
+    assert!(unsafe { Foo::from_view(&view) }.is_err());
+    let mut bytes: [u8; 256] = [0; 256];
+    let mut l = 256;
+    assert_eq!(
+        unsafe { test_print_foo_view(&view, bytes.as_mut_ptr(), &mut l) },
+        -1
+    );
+
+    view.id3[1] = remote(StrView::from("ok"));
+    assert!(unsafe { Foo::from_view(&view) }.is_ok());
+}
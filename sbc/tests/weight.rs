@@ -1,6 +1,4 @@
-pub use serde;
 use serde_bindgen_core::binding;
-use serde_json_core;
 
 #[binding(prefix = "test")]
 pub struct Remote<'a> {
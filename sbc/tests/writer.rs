@@ -0,0 +1,131 @@
+// This file is part of the serde-bindgen-core libraries
+// Copyright (C) 2022  Altronix Corp. <thomas.chiantia@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// @author Thomas Chiantia <thomas.chiantia@gmail.com>
+// @date 2022
+
+use serde_bindgen_core::binding;
+use serde_bindgen_core::writer;
+
+#[binding(prefix = "test")]
+pub struct Remote<'a> {
+    /// sbc: len = 16
+    pub id0: &'a str,
+    pub id1: [i32; 2],
+}
+
+#[binding(prefix = "test")]
+pub struct Foo<'a> {
+    pub id0: u8,
+    pub id1: i8,
+    pub id2: u32,
+    pub id3: i32,
+    pub id4: bool,
+    /// sbc: len = 16
+    pub id5: &'a str,
+    pub id6: Remote<'a>,
+    pub id7: [Remote<'a>; 2],
+    /// sbc: len = 8
+    pub id8: [&'a str; 2],
+}
+
+const ROOT: Foo = Foo {
+    id0: 255,
+    id1: -128,
+    id2: 4294967295,
+    id3: -2147483648,
+    id4: true,
+    id5: "esc \"\\\n\t\u{1}",
+    id6: Remote {
+        id0: "a",
+        id1: [0, -1],
+    },
+    id7: [
+        Remote {
+            id0: "b",
+            id1: [1, 2],
+        },
+        Remote {
+            id0: "c",
+            id1: [3, 4],
+        },
+    ],
+    id8: ["d", "e"],
+};
+
+fn expect(data: &Foo) -> String {
+    let mut bytes: [u8; 512] = [0; 512];
+    let len = serde_json_core::to_slice(data, &mut bytes).unwrap();
+    String::from_utf8(bytes[0..len].to_vec()).unwrap()
+}
+
+#[test]
+fn can_write_fmt() {
+    let mut s = String::new();
+    let len = writer::to_fmt(&ROOT, &mut s).unwrap();
+    assert_eq!(len, s.len());
+    assert_eq!(s, expect(&ROOT));
+}
+
+#[test]
+fn can_write_io() {
+    let mut bytes: [u8; 512] = [0; 512];
+    let mut slice: &mut [u8] = &mut bytes;
+    let len = writer::to_io(&ROOT, &mut slice).unwrap();
+    assert_eq!(std::str::from_utf8(&bytes[0..len]).unwrap(), expect(&ROOT));
+}
+
+#[test]
+fn can_write_io_short() {
+    let mut bytes: [u8; 8] = [0; 8];
+    let mut slice: &mut [u8] = &mut bytes;
+    assert_eq!(writer::to_io(&ROOT, &mut slice), Err(writer::Error::Write));
+}
+
+extern "C" fn collect(ctx: *mut core::ffi::c_void, bytes: *const u8, len: usize) -> i32 {
+    let out = unsafe { &mut *(ctx as *mut Vec<u8>) };
+    out.extend_from_slice(unsafe { std::slice::from_raw_parts(bytes, len) });
+    0
+}
+
+extern "C" fn refuse(_ctx: *mut core::ffi::c_void, _bytes: *const u8, _len: usize) -> i32 {
+    -1
+}
+
+#[test]
+fn can_write_owned_callback() {
+    let mut owned = FooOwned::default();
+    test_copy_foo(&mut owned, &ROOT);
+
+    let mut out: Vec<u8> = Vec::new();
+    let ctx = &mut out as *mut Vec<u8> as *mut core::ffi::c_void;
+    let ret = test_write_foo_owned(&owned, collect, ctx);
+    assert_eq!(ret, 0);
+
+    let mut bytes: [u8; 512] = [0; 512];
+    let mut len = 512;
+    let ret = unsafe { test_print_foo_owned(&owned, bytes.as_mut_ptr(), &mut len) };
+    assert_eq!(ret, 0);
+    assert_eq!(out, &bytes[0..len]);
+}
+
+#[test]
+fn can_abort_write_owned_callback() {
+    let mut owned = FooOwned::default();
+    test_init_foo(&mut owned);
+    let ret = test_write_foo_owned(&owned, refuse, core::ptr::null_mut());
+    assert_eq!(ret, -1);
+}